@@ -3,7 +3,10 @@
  * All rights reserved.
  */
 /// This module for converting time from blueprint(c#) time to rust NaiveDateTime and vice versa
-use chrono::{Duration, NaiveDate, NaiveDateTime, Utc};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 
 /// Returns C# epoch – 0001-01-01 00:00:00.
 fn csharp_epoch() -> NaiveDateTime {
@@ -13,26 +16,137 @@ fn csharp_epoch() -> NaiveDateTime {
         .unwrap()
 }
 
-/// Converts the number of C# “ticks” (1 tick = 100 nanoseconds) to NaiveDateTime.
-/// The function returns the time: epoch + number of seconds and microseconds calculated from ticks.
-pub fn csharp_to_datetime(csharp_ticks: i64) -> NaiveDateTime {
-    let seconds = csharp_ticks / 10_000_000;
-    let residual = csharp_ticks % 10_000_000;
-    // 1 tick = 100 nanoseconds, а 1 microsecond = 10 ticks.
-    let microseconds = residual / 10;
-    csharp_epoch() + Duration::seconds(seconds) + Duration::microseconds(microseconds)
+/// C# `DateTimeKind`, stored in the two upper bits of `DateTime.ToBinary()`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum DateTimeKind {
+    #[default]
+    Unspecified,
+    Utc,
+    Local,
 }
 
-/// Converts NaiveDateTime to C# tix number.
-/// Fractional seconds (microseconds) are discarded here.
-pub fn datetime_to_csharp(dt: NaiveDateTime) -> i64 {
-    let duration = dt.signed_duration_since(csharp_epoch());
-    duration.num_seconds() * 10_000_000
+/// C# `DateTime` as a 64-bit value, kept verbatim so it's written back unchanged.
+///
+/// The lower 62 bits hold ticks (100 nanoseconds) since 0001-01-01 00:00:00, the upper two the
+/// [`DateTimeKind`] the way `DateTime.ToBinary()` writes it. Blueprint strings hold plain
+/// `DateTime.Ticks`, which is always [`DateTimeKind::Unspecified`] wall-clock time.
+#[derive(
+    Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct CSharpTicks(i64);
+
+impl CSharpTicks {
+    pub const TICKS_PER_SECOND: i64 = 10_000_000;
+    const NANOS_PER_TICK: i64 = 100;
+    const TICKS_MASK: i64 = 0x3FFF_FFFF_FFFF_FFFF;
+    const UTC_FLAG: i64 = 0x4000_0000_0000_0000;
+    const LOCAL_FLAG: i64 = i64::MIN;
+
+    /// Wraps the value exactly as it was written.
+    pub const fn from_raw(raw: i64) -> Self {
+        CSharpTicks(raw)
+    }
+
+    /// The value exactly as it was read, including the kind bits.
+    pub const fn raw(self) -> i64 {
+        self.0
+    }
+
+    /// Creates the value out of `ticks` since the C# epoch, which must fit into 62 bits.
+    pub fn new(ticks: i64, kind: DateTimeKind) -> Option<Self> {
+        if ticks & !Self::TICKS_MASK != 0 {
+            return None;
+        }
+        let flag = match kind {
+            DateTimeKind::Unspecified => 0,
+            DateTimeKind::Utc => Self::UTC_FLAG,
+            DateTimeKind::Local => Self::LOCAL_FLAG,
+        };
+        Some(CSharpTicks(ticks | flag))
+    }
+
+    /// Ticks since the C# epoch without the kind bits.
+    pub const fn ticks(self) -> i64 {
+        self.0 & Self::TICKS_MASK
+    }
+
+    /// Kind of the value. C# uses both bits set for local time in the ambiguous DST hour.
+    pub fn kind(self) -> DateTimeKind {
+        match (self.0 as u64) >> 62 {
+            0 => DateTimeKind::Unspecified,
+            1 => DateTimeKind::Utc,
+            _ => DateTimeKind::Local,
+        }
+    }
+
+    /// Wall-clock time of the value, exact to the tick.
+    pub fn to_naive(self) -> NaiveDateTime {
+        let ticks = self.ticks();
+        csharp_epoch()
+            + Duration::seconds(ticks / Self::TICKS_PER_SECOND)
+            + Duration::nanoseconds(ticks % Self::TICKS_PER_SECOND * Self::NANOS_PER_TICK)
+    }
+
+    /// Converts wall-clock time, nanoseconds below a tick are truncated.
+    ///
+    /// Returns `None` for times before the C# epoch or too far in the future.
+    pub fn from_naive(dt: NaiveDateTime, kind: DateTimeKind) -> Option<Self> {
+        let duration = dt.signed_duration_since(csharp_epoch());
+        let seconds = duration.num_seconds();
+        let nanos = duration.subsec_nanos() as i64;
+        if seconds < 0 {
+            return None;
+        }
+        let ticks = seconds
+            .checked_mul(Self::TICKS_PER_SECOND)?
+            .checked_add(nanos / Self::NANOS_PER_TICK)?;
+        Self::new(ticks, kind)
+    }
+
+    /// Point in time of the value.
+    ///
+    /// Unspecified values are taken as UTC, local ones are resolved in the local time zone,
+    /// which fails for times skipped by a DST change.
+    pub fn to_utc(self) -> Option<DateTime<Utc>> {
+        match self.kind() {
+            DateTimeKind::Unspecified | DateTimeKind::Utc => Some(self.to_naive().and_utc()),
+            DateTimeKind::Local => Local
+                .from_local_datetime(&self.to_naive())
+                .earliest()
+                .map(|dt| dt.with_timezone(&Utc)),
+        }
+    }
+
+    /// UTC time with [`DateTimeKind::Utc`].
+    pub fn from_utc(dt: DateTime<Utc>) -> Option<Self> {
+        Self::from_naive(dt.naive_utc(), DateTimeKind::Utc)
+    }
+
+    /// Local time with [`DateTimeKind::Local`].
+    pub fn from_local(dt: DateTime<Local>) -> Option<Self> {
+        Self::from_naive(dt.naive_local(), DateTimeKind::Local)
+    }
+
+    /// Current UTC time as an unspecified value, the way blueprint timestamps are stored.
+    pub fn now() -> Self {
+        Self::from_naive(Utc::now().naive_utc(), DateTimeKind::Unspecified)
+            .expect("current time is within the C# range")
+    }
 }
 
-/// Returns the current time (UTC) as the number of C# ticks.
-pub fn csharp_now() -> i64 {
-    datetime_to_csharp(Utc::now().naive_utc())
+impl fmt::Display for CSharpTicks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for CSharpTicks {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(CSharpTicks)
+    }
 }
 
 #[cfg(test)]
@@ -42,13 +156,13 @@ mod tests {
 
     #[test]
     fn test_epoch() {
-        let dt = csharp_to_datetime(0);
+        let dt = CSharpTicks::from_raw(0).to_naive();
         assert_eq!(dt, csharp_epoch());
     }
 
     #[test]
     fn test_one_second() {
-        let dt = csharp_to_datetime(10_000_000);
+        let dt = CSharpTicks::from_raw(10_000_000).to_naive();
         let expected = csharp_epoch() + Duration::seconds(1);
         assert_eq!(dt, expected);
     }
@@ -59,15 +173,72 @@ mod tests {
             .unwrap()
             .and_hms_opt(15, 30, 45)
             .unwrap();
-        let ticks = datetime_to_csharp(dt);
-        let dt_converted = csharp_to_datetime(ticks);
+        let ticks = CSharpTicks::from_naive(dt, DateTimeKind::Unspecified).unwrap();
+        let dt_converted = ticks.to_naive();
         assert_eq!(dt, dt_converted);
     }
 
+    #[test]
+    fn test_tick_precision() {
+        let ticks = CSharpTicks::from_raw(638391476082347356);
+        let dt = ticks.to_naive();
+        assert_eq!(
+            dt,
+            NaiveDate::from_ymd_opt(2023, 12, 26)
+                .unwrap()
+                .and_hms_nano_opt(0, 33, 28, 234_735_600)
+                .unwrap()
+        );
+        assert_eq!(
+            CSharpTicks::from_naive(dt, DateTimeKind::Unspecified),
+            Some(ticks)
+        );
+        // below a tick is truncated
+        assert_eq!(
+            CSharpTicks::from_naive(dt + Duration::nanoseconds(99), DateTimeKind::Unspecified),
+            Some(ticks)
+        );
+        assert_eq!(
+            CSharpTicks::from_naive(
+                csharp_epoch() - Duration::nanoseconds(100),
+                DateTimeKind::Utc
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_kind() {
+        let dt = CSharpTicks::from_raw(638391476082347356).to_naive();
+        let utc = CSharpTicks::from_naive(dt, DateTimeKind::Utc).unwrap();
+        assert_eq!(utc.kind(), DateTimeKind::Utc);
+        assert_eq!(utc.ticks(), 638391476082347356);
+        assert_eq!(utc.raw(), 638391476082347356 | 0x4000_0000_0000_0000);
+        assert_eq!(utc.to_naive(), dt);
+        assert_eq!(utc.to_utc(), Some(dt.and_utc()));
+        assert_eq!(CSharpTicks::from_utc(dt.and_utc()), Some(utc));
+
+        let local = CSharpTicks::new(utc.ticks(), DateTimeKind::Local).unwrap();
+        assert!(local.raw() < 0);
+        assert_eq!(local.kind(), DateTimeKind::Local);
+        assert_eq!(local.to_naive(), dt);
+        // C# marks local times in the ambiguous DST hour with both bits
+        assert_eq!(
+            CSharpTicks::from_raw(local.raw() | utc.raw()).kind(),
+            DateTimeKind::Local
+        );
+
+        assert_eq!(CSharpTicks::new(-1, DateTimeKind::Unspecified), None);
+        assert_eq!(
+            "638391476082347356".parse::<CSharpTicks>().unwrap(),
+            CSharpTicks::from_raw(638391476082347356)
+        );
+        assert_eq!(local.to_string().parse::<CSharpTicks>().unwrap(), local);
+    }
+
     #[test]
     fn test_csharp_now() {
-        let ticks = csharp_now();
-        let dt = csharp_to_datetime(ticks);
+        let dt = CSharpTicks::now().to_naive();
         let now = Utc::now().naive_utc();
         let diff = (now - dt).num_seconds().abs();
         assert!(diff < 5, "Difference too high: {} seconds", diff);
@@ -5,7 +5,7 @@
 
 #![allow(dead_code)]
 
-use crate::errors::UnknownDysonSphereBuildingType;
+use crate::errors::BlueprintError;
 
 macro_rules! define_building_type_enum {
     ($name:ident, $($variant:ident = $value:expr),* $(,)?) => {
@@ -19,12 +19,12 @@ macro_rules! define_building_type_enum {
         }
 
         impl TryFrom<i32> for $name {
-            type Error = UnknownDysonSphereBuildingType;
+            type Error = BlueprintError;
 
             fn try_from(value: i32) -> Result<Self, Self::Error> {
                 match value {
                     $($value => Ok($name::$variant),)*
-                    _ => Err(UnknownDysonSphereBuildingType(value)),
+                    _ => Err(BlueprintError::UnknownBuildingType(value)),
                 }
             }
         }
@@ -5,7 +5,7 @@
 
 #![allow(dead_code)]
 
-use crate::errors::UnknownDysonSphereIconLayout;
+use crate::errors::BlueprintError;
 
 macro_rules! define_icon_enum {
     ($name:ident, $($variant:ident = $value:expr),* $(,)?) => {
@@ -19,12 +19,12 @@ macro_rules! define_icon_enum {
         }
 
         impl TryFrom<i32> for $name {
-            type Error = UnknownDysonSphereIconLayout;
+            type Error = BlueprintError;
 
             fn try_from(value: i32) -> Result<Self, Self::Error> {
                 match value {
                     $($value => Ok($name::$variant),)*
-                    _ => Err(UnknownDysonSphereIconLayout(value)),
+                    _ => Err(BlueprintError::UnknownIconLayout(value)),
                 }
             }
         }
@@ -5,7 +5,7 @@
 
 #![allow(dead_code)]
 
-use crate::errors::UnknownDysonSphereItem;
+use crate::errors::BlueprintError;
 
 macro_rules! define_item_enum {
     ($name:ident, $($variant:ident = $value:expr),* $(,)?) => {
@@ -19,12 +19,15 @@ macro_rules! define_item_enum {
         }
 
         impl TryFrom<i32> for $name {
-            type Error = UnknownDysonSphereItem;
+            type Error = BlueprintError;
 
             fn try_from(value: i32) -> Result<Self, Self::Error> {
                 match value {
                     $($value => Ok($name::$variant),)*
-                    _ => Err(UnknownDysonSphereItem(value)),
+                    _ => Err(BlueprintError::UnknownItem {
+                        id: value,
+                        location: None,
+                    }),
                 }
             }
         }
@@ -125,9 +128,12 @@ define_item_enum!(
     SorterMKII = 2012,
     SorterMKIII = 2013,
     Splitter = 2020,
+    TrafficMonitor = 2030,
+    AutomaticPiler = 2040,
     StorageMKI = 2101,
     StorageMKII = 2102,
     StorageTank = 2106,
+    LogisticsDistributor = 2107,
     AssemblingMachineMkI = 2303,
     AssemblingMachineMkII = 2304,
     AssemblingMachineMkIII = 2305,
@@ -137,10 +143,12 @@ define_item_enum!(
     TeslaTower = 2201,
     WirelessPowerTower = 2202,
     SatelliteSubstation = 2212,
+    GeothermalPowerStation = 2213,
     WindTurbine = 2203,
     ThermalPowerStation = 2204,
     MiniFusionPowerStation = 2211,
     MiningMachine = 2301,
+    AdvancedMiningMachine = 2316,
     Smelter = 2302,
     OilExtractor = 2307,
     OilRefinery = 2308,
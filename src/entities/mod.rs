@@ -3,6 +3,8 @@
  * All rights reserved.
  */
 
-pub(crate) mod building_types;
-pub(crate) mod icon_layout;
-pub(crate) mod item;
+pub mod building_types;
+pub mod icon_layout;
+pub mod item;
+pub mod recipe;
+pub mod signal;
@@ -0,0 +1,372 @@
+/*
+ * Copyright (c) 2025. sakost aka Konstantin Sazhenov
+ * All rights reserved.
+ */
+
+use crate::entities::item::DysonSphereItem;
+use crate::errors::BlueprintError;
+
+/// Kind of buildings a recipe is made in.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RecipeClass {
+    Smelting,
+    Assembling,
+    Chemical,
+    Refining,
+    Particle,
+    Fractionation,
+    Research,
+}
+
+impl RecipeClass {
+    /// Class of the recipes `item` makes, `None` if it doesn't run recipes.
+    ///
+    /// Fractionators and labs aren't decoded as recipe buildings, so they're left out here and only
+    /// checked by [`Recipe::can_run_in`].
+    pub fn of(item: DysonSphereItem) -> Option<Self> {
+        if item.is_smelter() {
+            return Some(RecipeClass::Smelting);
+        }
+        if item.is_assembling_machine() {
+            return Some(RecipeClass::Assembling);
+        }
+        match item {
+            DysonSphereItem::ChemicalPlant => Some(RecipeClass::Chemical),
+            DysonSphereItem::OilRefinery => Some(RecipeClass::Refining),
+            DysonSphereItem::MiniatureParticleCollider => Some(RecipeClass::Particle),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! define_recipe_enum {
+    ($name:ident, $(
+        $variant:ident = $value:expr, $class:ident, $ticks:expr,
+        [$($input:ident: $input_count:expr),*] => [$($output:ident: $output_count:expr),*];
+    )*) => {
+        use ::strum::IntoStaticStr;
+
+        #[repr(i32)]
+        #[derive(Debug, PartialEq, Eq, Clone, Copy, IntoStaticStr)]
+        pub enum $name {
+            $($variant = $value),*
+        }
+
+        impl $name {
+            /// Kind of buildings the recipe is made in.
+            pub fn class(&self) -> RecipeClass {
+                match self {
+                    $($name::$variant => RecipeClass::$class,)*
+                }
+            }
+
+            /// Time of one run in ticks, 60 ticks a second.
+            pub fn ticks(&self) -> u32 {
+                match self {
+                    $($name::$variant => $ticks,)*
+                }
+            }
+
+            /// Items and their counts used by one run.
+            pub fn inputs(&self) -> &'static [(DysonSphereItem, u32)] {
+                match self {
+                    $($name::$variant => &[$((DysonSphereItem::$input, $input_count)),*],)*
+                }
+            }
+
+            /// Items and their counts made by one run.
+            pub fn outputs(&self) -> &'static [(DysonSphereItem, u32)] {
+                match self {
+                    $($name::$variant => &[$((DysonSphereItem::$output, $output_count)),*],)*
+                }
+            }
+        }
+
+        impl TryFrom<i32> for $name {
+            type Error = BlueprintError;
+
+            fn try_from(value: i32) -> Result<Self, Self::Error> {
+                match value {
+                    $($value => Ok($name::$variant),)*
+                    _ => Err(BlueprintError::UnknownRecipe(value)),
+                }
+            }
+        }
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                match self {
+                    $($name::$variant => write!(f, "{}", stringify!($variant)),)*
+                }
+            }
+        }
+    }
+}
+
+define_recipe_enum!(
+    Recipe,
+    IronIngot = 1, Smelting, 60, [IronOre: 1] => [IronIngot: 1];
+    Magnet = 2, Smelting, 90, [IronOre: 1] => [Magnet: 1];
+    CopperIngot = 3, Smelting, 60, [CopperOre: 1] => [CopperIngot: 1];
+    StoneBrick = 4, Smelting, 60, [Stone: 1] => [StoneBrick: 1];
+    Gear = 5, Assembling, 60, [IronIngot: 1] => [Gear: 1];
+    MagneticCoil = 6, Assembling, 60, [Magnet: 2, CopperIngot: 1] => [MagneticCoil: 2];
+    WindTurbine = 7, Assembling, 240,
+        [IronIngot: 6, Gear: 1, MagneticCoil: 3] => [WindTurbine: 1];
+    TeslaTower = 8, Assembling, 60, [IronIngot: 2, MagneticCoil: 1] => [TeslaTower: 1];
+    ElectromagneticMatrix = 9, Research, 180,
+        [MagneticCoil: 1, CircuitBoard: 1] => [ElectromagneticMatrix: 1];
+    MatrixLab = 10, Assembling, 180,
+        [IronIngot: 8, Glass: 4, CircuitBoard: 4, MagneticCoil: 4] => [MatrixLab: 1];
+    Prism = 11, Assembling, 120, [Glass: 3] => [Prism: 2];
+    PlasmaExciter = 12, Assembling, 120, [MagneticCoil: 4, Prism: 2] => [PlasmaExciter: 1];
+    WirelessPowerTower = 13, Assembling, 180,
+        [TeslaTower: 1, PlasmaExciter: 3] => [WirelessPowerTower: 1];
+    OilExtractor = 14, Assembling, 480,
+        [Steel: 12, StoneBrick: 12, CircuitBoard: 6, PlasmaExciter: 4] => [OilExtractor: 1];
+    OilRefinery = 15, Assembling, 360,
+        [Steel: 10, StoneBrick: 10, CircuitBoard: 6, PlasmaExciter: 6] => [OilRefinery: 1];
+    PlasmaRefining = 16, Refining, 240, [CrudeOil: 2] => [RefinedOil: 2, Hydrogen: 1];
+    EnergeticGraphite = 17, Smelting, 120, [Coal: 2] => [EnergeticGraphite: 1];
+    EnergyMatrix = 18, Research, 360, [EnergeticGraphite: 2, Hydrogen: 2] => [EnergyMatrix: 1];
+    HydrogenFuelRod = 19, Chemical, 360,
+        [TitaniumIngot: 1, Hydrogen: 10] => [HydrogenFuelRod: 2];
+    Thruster = 20, Assembling, 240, [Steel: 2, CopperIngot: 3] => [Thruster: 1];
+    ReinforcedThruster = 21, Assembling, 360,
+        [TitaniumAlloy: 5, ElectromagneticTurbine: 5] => [ReinforcedThruster: 1];
+    ChemicalPlant = 22, Assembling, 300,
+        [Steel: 8, StoneBrick: 8, Glass: 8, CircuitBoard: 2] => [ChemicalPlant: 1];
+    Plastic = 23, Chemical, 180, [RefinedOil: 2, EnergeticGraphite: 1] => [Plastic: 1];
+    SulfuricAcid = 24, Chemical, 360,
+        [RefinedOil: 6, Stone: 8, Water: 4] => [SulfuricAcid: 4];
+    OrganicCrystal = 25, Chemical, 360,
+        [Plastic: 2, RefinedOil: 1, Water: 1] => [OrganicCrystal: 1];
+    TitaniumCrystal = 26, Assembling, 240,
+        [OrganicCrystal: 1, TitaniumIngot: 3] => [TitaniumCrystal: 1];
+    StructureMatrix = 27, Research, 480, [Diamond: 1, TitaniumCrystal: 1] => [StructureMatrix: 1];
+    CasimirCrystal = 28, Assembling, 240,
+        [TitaniumCrystal: 1, Graphene: 2, Hydrogen: 12] => [CasimirCrystal: 1];
+    CasimirCrystalAdvanced = 29, Assembling, 240,
+        [OpticalGratingCrystal: 8, Graphene: 2, Hydrogen: 12] => [CasimirCrystal: 1];
+    TitaniumGlass = 30, Assembling, 300,
+        [Glass: 2, TitaniumIngot: 2, Water: 2] => [TitaniumGlass: 2];
+    Graphene = 31, Chemical, 180, [EnergeticGraphite: 3, SulfuricAcid: 1] => [Graphene: 2];
+    GrapheneAdvanced = 32, Chemical, 120, [FireIce: 2] => [Graphene: 2, Hydrogen: 1];
+    CarbonNanotube = 33, Chemical, 240, [Graphene: 3, TitaniumIngot: 1] => [CarbonNanotube: 2];
+    SiliconOre = 34, Smelting, 600, [Stone: 10] => [SiliconOre: 1];
+    CarbonNanotubeAdvanced = 35, Chemical, 240,
+        [SpiniformStalagmiteCrystal: 6] => [CarbonNanotube: 2];
+    ParticleBroadband = 36, Assembling, 480,
+        [CarbonNanotube: 2, CrystalSilicon: 2, Plastic: 1] => [ParticleBroadband: 1];
+    CrystalSilicon = 37, Smelting, 120, [HighPuritySilicon: 1] => [CrystalSilicon: 1];
+    PlaneFilter = 38, Assembling, 720, [CasimirCrystal: 1, TitaniumGlass: 2] => [PlaneFilter: 1];
+    MiniatureParticleCollider = 39, Assembling, 900,
+        [TitaniumAlloy: 20, FrameMaterial: 20, SuperMagneticRing: 25, Graphene: 10, Processor: 8]
+        => [MiniatureParticleCollider: 1];
+    Deuterium = 40, Particle, 150, [Hydrogen: 10] => [Deuterium: 5];
+    DeuteronFuelRod = 41, Assembling, 720,
+        [TitaniumAlloy: 1, Deuterium: 20, SuperMagneticRing: 1] => [DeuteronFuelRod: 2];
+    AnnihilationConstraintSphere = 42, Assembling, 1200,
+        [ParticleContainer: 1, Processor: 1] => [AnnihilationConstraintSphere: 1];
+    ArtificialStar = 43, Assembling, 1800,
+        [TitaniumAlloy: 20, FrameMaterial: 20, AnnihilationConstraintSphere: 10, QuantumChip: 10]
+        => [ArtificialStar: 1];
+    AntimatterFuelRod = 44, Assembling, 1440,
+        [Antimatter: 12, Hydrogen: 12, AnnihilationConstraintSphere: 1, TitaniumAlloy: 1]
+        => [AntimatterFuelRod: 2];
+    AssemblingMachineMkI = 45, Assembling, 120,
+        [IronIngot: 4, Gear: 8, CircuitBoard: 4] => [AssemblingMachineMkI: 1];
+    AssemblingMachineMkII = 46, Assembling, 180,
+        [AssemblingMachineMkI: 1, Graphene: 8, Processor: 4] => [AssemblingMachineMkII: 1];
+    AssemblingMachineMkIII = 47, Assembling, 240,
+        [AssemblingMachineMkII: 1, ParticleBroadband: 8, QuantumChip: 2]
+        => [AssemblingMachineMkIII: 1];
+    MiningMachine = 48, Assembling, 180,
+        [IronIngot: 4, CircuitBoard: 2, MagneticCoil: 2, Gear: 2] => [MiningMachine: 1];
+    WaterPump = 49, Assembling, 240,
+        [IronIngot: 8, StoneBrick: 4, ElectricMotor: 4, CircuitBoard: 2] => [WaterPump: 1];
+    CircuitBoard = 50, Assembling, 60, [IronIngot: 2, CopperIngot: 1] => [CircuitBoard: 2];
+    Processor = 51, Assembling, 180,
+        [CircuitBoard: 2, MicrocrystallineComponent: 2] => [Processor: 1];
+    QuantumChip = 52, Assembling, 360, [Processor: 2, PlaneFilter: 2] => [QuantumChip: 1];
+    MicrocrystallineComponent = 53, Assembling, 120,
+        [HighPuritySilicon: 2, CopperIngot: 1] => [MicrocrystallineComponent: 1];
+    OrganicCrystalOriginal = 54, Assembling, 360,
+        [Log: 20, PlantFuel: 30, Water: 10] => [OrganicCrystal: 1];
+    InformationMatrix = 55, Research, 600,
+        [Processor: 2, ParticleBroadband: 1] => [InformationMatrix: 1];
+    ArcSmelter = 56, Assembling, 180,
+        [IronIngot: 4, StoneBrick: 2, CircuitBoard: 4, MagneticCoil: 2] => [Smelter: 1];
+    Glass = 57, Smelting, 120, [Stone: 2] => [Glass: 1];
+    XRayCracking = 58, Refining, 240,
+        [RefinedOil: 1, Hydrogen: 2] => [Hydrogen: 3, EnergeticGraphite: 1];
+    HighPuritySilicon = 59, Smelting, 120, [SiliconOre: 2] => [HighPuritySilicon: 1];
+    Diamond = 60, Smelting, 120, [EnergeticGraphite: 1] => [Diamond: 1];
+    DiamondAdvanced = 61, Smelting, 90, [KimberliteOre: 1] => [Diamond: 2];
+    CrystalSiliconAdvanced = 62, Assembling, 90, [FractalSilicon: 1] => [CrystalSilicon: 2];
+    Steel = 63, Smelting, 180, [IronIngot: 3] => [Steel: 1];
+    ThermalPowerStation = 64, Assembling, 300,
+        [IronIngot: 10, StoneBrick: 4, Gear: 4, MagneticCoil: 4] => [ThermalPowerStation: 1];
+    TitaniumIngot = 65, Smelting, 120, [TitaniumOre: 2] => [TitaniumIngot: 1];
+    TitaniumAlloy = 66, Smelting, 720,
+        [TitaniumIngot: 4, Steel: 4, SulfuricAcid: 8] => [TitaniumAlloy: 4];
+    SolarPanel = 67, Assembling, 360,
+        [CopperIngot: 10, HighPuritySilicon: 10, CircuitBoard: 5] => [SolarPanel: 1];
+    PhotonCombiner = 68, Assembling, 180, [Prism: 2, CircuitBoard: 1] => [PhotonCombiner: 1];
+    PhotonCombinerAdvanced = 69, Assembling, 180,
+        [OpticalGratingCrystal: 1, CircuitBoard: 1] => [PhotonCombiner: 1];
+    SolarSail = 70, Assembling, 240, [Graphene: 1, PhotonCombiner: 1] => [SolarSail: 2];
+    EMRailEjector = 71, Assembling, 360,
+        [Steel: 20, Gear: 20, Processor: 5, SuperMagneticRing: 10] => [EMRailEjector: 1];
+    RayReceiver = 72, Assembling, 480,
+        [Steel: 20, HighPuritySilicon: 20, PhotonCombiner: 10, Processor: 5, SuperMagneticRing: 20]
+        => [RayReceiver: 1];
+    SatelliteSubstation = 73, Assembling, 300,
+        [WirelessPowerTower: 1, SuperMagneticRing: 10, FrameMaterial: 2]
+        => [SatelliteSubstation: 1];
+    MassEnergyStorage = 74, Particle, 120, [CriticalPhoton: 2] => [Antimatter: 2, Hydrogen: 2];
+    UniverseMatrix = 75, Research, 900,
+        [ElectromagneticMatrix: 1, EnergyMatrix: 1, StructureMatrix: 1, InformationMatrix: 1,
+            GravityMatrix: 1, Antimatter: 1] => [UniverseMatrix: 1];
+    Accumulator = 76, Assembling, 300,
+        [IronIngot: 6, SuperMagneticRing: 1, CrystalSilicon: 6] => [Accumulator: 1];
+    EnergyExchanger = 77, Assembling, 900,
+        [TitaniumAlloy: 40, Steel: 40, Processor: 40, ParticleContainer: 8] => [EnergyExchanger: 1];
+    SpaceWarper = 78, Assembling, 600, [GravitonLens: 1] => [SpaceWarper: 1];
+    SpaceWarperAdvanced = 79, Assembling, 600, [GravityMatrix: 1] => [SpaceWarper: 8];
+    FrameMaterial = 80, Assembling, 360,
+        [CarbonNanotube: 4, TitaniumAlloy: 1, HighPuritySilicon: 1] => [FrameMaterial: 1];
+    DysonSphereComponent = 81, Assembling, 480,
+        [FrameMaterial: 3, SolarSail: 3, Processor: 3] => [DysonSphereComponent: 1];
+    VerticalLaunchingSilo = 82, Assembling, 1800,
+        [TitaniumAlloy: 80, FrameMaterial: 30, GravitonLens: 20, QuantumChip: 10]
+        => [VerticalLaunchingSilo: 1];
+    SmallCarrierRocket = 83, Assembling, 360,
+        [DysonSphereComponent: 2, DeuteronFuelRod: 4, QuantumChip: 2] => [SmallCarrierRocket: 1];
+    ConveyorBeltMKI = 84, Assembling, 60, [IronIngot: 2, Gear: 1] => [ConveyorBeltMKI: 3];
+    SorterMKI = 85, Assembling, 60, [IronIngot: 1, CircuitBoard: 1] => [SorterMKI: 1];
+    StorageMKI = 86, Assembling, 120, [IronIngot: 4, StoneBrick: 4] => [StorageMKI: 1];
+    Splitter = 87, Assembling, 120, [IronIngot: 3, Gear: 2, CircuitBoard: 1] => [Splitter: 1];
+    SorterMKII = 88, Assembling, 60, [SorterMKI: 2, ElectricMotor: 1] => [SorterMKII: 2];
+    ConveyorBeltMKII = 89, Assembling, 60,
+        [ConveyorBeltMKI: 3, ElectromagneticTurbine: 1] => [ConveyorBeltMKII: 3];
+    SorterMKIII = 90, Assembling, 60,
+        [SorterMKII: 2, ElectromagneticTurbine: 1] => [SorterMKIII: 2];
+    StorageMKII = 91, Assembling, 120, [Steel: 8, StoneBrick: 8] => [StorageMKII: 1];
+    ConveyorBeltMKIII = 92, Assembling, 60,
+        [ConveyorBeltMKII: 3, SuperMagneticRing: 1, Graphene: 1] => [ConveyorBeltMKIII: 3];
+    PlanetaryLogisticsStation = 93, Assembling, 1200,
+        [Steel: 40, TitaniumIngot: 40, Processor: 40, ParticleContainer: 20]
+        => [PlanetaryLogisticsStation: 1];
+    LogisticsDrone = 94, Assembling, 240,
+        [IronIngot: 5, Processor: 2, Thruster: 2] => [LogisticsDrone: 1];
+    InterstellarLogisticsStation = 95, Assembling, 1800,
+        [PlanetaryLogisticsStation: 1, TitaniumAlloy: 40, ParticleContainer: 20]
+        => [InterstellarLogisticsStation: 1];
+    LogisticsVessel = 96, Assembling, 360,
+        [TitaniumAlloy: 10, Processor: 10, ReinforcedThruster: 2] => [LogisticsVessel: 1];
+    ElectricMotor = 97, Assembling, 120,
+        [IronIngot: 2, Gear: 1, MagneticCoil: 1] => [ElectricMotor: 1];
+    ElectromagneticTurbine = 98, Assembling, 120,
+        [ElectricMotor: 2, MagneticCoil: 2] => [ElectromagneticTurbine: 1];
+    ParticleContainer = 99, Assembling, 240,
+        [ElectromagneticTurbine: 2, CopperIngot: 2, Graphene: 2] => [ParticleContainer: 1];
+    ParticleContainerAdvanced = 100, Assembling, 240,
+        [UnipolarMagnet: 10, CopperIngot: 2] => [ParticleContainer: 1];
+    GravitonLens = 101, Assembling, 360, [Diamond: 4, StrangeMatter: 1] => [GravitonLens: 1];
+    GravityMatrix = 102, Research, 1440, [GravitonLens: 1, QuantumChip: 1] => [GravityMatrix: 2];
+    SuperMagneticRing = 103, Assembling, 180,
+        [ElectromagneticTurbine: 2, Magnet: 3, EnergeticGraphite: 1] => [SuperMagneticRing: 1];
+    StrangeMatter = 104, Particle, 480,
+        [ParticleContainer: 2, IronIngot: 2, Deuterium: 10] => [StrangeMatter: 1];
+    ProliferatorMkI = 106, Assembling, 30, [Coal: 1] => [AccelerantMkI: 1];
+    ProliferatorMkII = 107, Assembling, 60, [AccelerantMkI: 2, Diamond: 1] => [AccelerantMkII: 1];
+    ProliferatorMkIII = 108, Assembling, 120,
+        [AccelerantMkII: 2, CarbonNanotube: 1] => [AccelerantMkIII: 1];
+    SprayCoater = 109, Assembling, 180,
+        [Steel: 4, PlasmaExciter: 2, CircuitBoard: 2, MicrocrystallineComponent: 2]
+        => [SprayCoater: 1];
+    Fractionator = 110, Assembling, 180,
+        [Steel: 8, StoneBrick: 4, Glass: 4, Processor: 1] => [Fractionator: 1];
+    OrbitalCollector = 111, Assembling, 1800,
+        [InterstellarLogisticsStation: 1, SuperMagneticRing: 50, ReinforcedThruster: 20,
+            AccumulatorFull: 20] => [OrbitalCollector: 1];
+    Foundation = 112, Assembling, 60, [StoneBrick: 3, Steel: 1] => [Foundation: 1];
+    MiniFusionPowerStation = 113, Assembling, 600,
+        [TitaniumAlloy: 12, SuperMagneticRing: 10, CarbonNanotube: 8, Processor: 4]
+        => [MiniFusionPowerStation: 1];
+    StorageTank = 114, Assembling, 120, [IronIngot: 8, StoneBrick: 4, Glass: 4] => [StorageTank: 1];
+    DeuteriumFractionation = 115, Fractionation, 60, [Hydrogen: 1] => [Deuterium: 1];
+    PlaneSmelter = 116, Assembling, 180,
+        [Smelter: 1, FrameMaterial: 5, PlaneFilter: 4, UnipolarMagnet: 15] => [PlaneSmelter: 1];
+    TrafficMonitor = 117, Assembling, 120,
+        [IronIngot: 3, Gear: 2, Glass: 1, CircuitBoard: 2] => [TrafficMonitor: 1];
+    GeothermalPowerStation = 118, Assembling, 360,
+        [Steel: 15, CopperIngot: 20, PhotonCombiner: 4, SuperMagneticRing: 1]
+        => [GeothermalPowerStation: 1];
+    AdvancedMiningMachine = 119, Assembling, 1200,
+        [TitaniumAlloy: 20, FrameMaterial: 10, SuperMagneticRing: 10, QuantumChip: 4,
+            OpticalGratingCrystal: 40] => [AdvancedMiningMachine: 1];
+    AutomaticPiler = 120, Assembling, 240,
+        [Steel: 3, Gear: 4, SuperMagneticRing: 1, Processor: 2] => [AutomaticPiler: 1];
+    ReformingRefine = 121, Refining, 240,
+        [RefinedOil: 2, Hydrogen: 1, Coal: 1] => [RefinedOil: 3];
+    LogisticsDistributor = 122, Assembling, 480,
+        [IronIngot: 8, PlasmaExciter: 4, Processor: 4] => [LogisticsDistributor: 1];
+    LogisticsBot = 123, Assembling, 120,
+        [IronIngot: 2, Processor: 1, Thruster: 1] => [LogisticsBot: 1];
+);
+
+impl Recipe {
+    /// Time of one run in seconds.
+    pub fn seconds(&self) -> f32 {
+        self.ticks() as f32 / 60.
+    }
+
+    /// Whether a building made of `item` can run the recipe.
+    pub fn can_run_in(&self, item: DysonSphereItem) -> bool {
+        match self.class() {
+            RecipeClass::Research => {
+                matches!(
+                    item,
+                    DysonSphereItem::MatrixLab | DysonSphereItem::SelfEvolutionLab
+                )
+            }
+            RecipeClass::Fractionation => item == DysonSphereItem::Fractionator,
+            class => RecipeClass::of(item) == Some(class),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recipe() {
+        let recipe = Recipe::try_from(50).unwrap();
+        assert_eq!(recipe, Recipe::CircuitBoard);
+        assert_eq!(
+            recipe.inputs(),
+            [
+                (DysonSphereItem::IronIngot, 2),
+                (DysonSphereItem::CopperIngot, 1)
+            ]
+        );
+        assert_eq!(recipe.outputs(), [(DysonSphereItem::CircuitBoard, 2)]);
+        assert_eq!(recipe.seconds(), 1.);
+        assert!(recipe.can_run_in(DysonSphereItem::AssemblingMachineMkIII));
+        assert!(!recipe.can_run_in(DysonSphereItem::Smelter));
+        assert!(Recipe::Glass.can_run_in(DysonSphereItem::NegentropySmelter));
+        assert!(Recipe::InformationMatrix.can_run_in(DysonSphereItem::MatrixLab));
+        assert_eq!(
+            RecipeClass::of(DysonSphereItem::ChemicalPlant),
+            Some(RecipeClass::Chemical)
+        );
+        assert!(Recipe::try_from(0).is_err());
+        assert_eq!(Recipe::try_from(18).unwrap(), Recipe::EnergyMatrix);
+        assert_eq!(Recipe::try_from(121).unwrap(), Recipe::ReformingRefine);
+        assert!(Recipe::GravitonLens.can_run_in(DysonSphereItem::AssemblingMachineMkII));
+        assert!(Recipe::DeuteriumFractionation.can_run_in(DysonSphereItem::Fractionator));
+        assert!(Recipe::try_from(105).is_err());
+    }
+}
@@ -0,0 +1,78 @@
+/*
+ * Copyright (c) 2025. sakost aka Konstantin Sazhenov
+ * All rights reserved.
+ */
+
+use crate::entities::item::DysonSphereItem;
+use crate::entities::recipe::Recipe;
+use serde::{Deserialize, Serialize};
+
+/// Icon shown on belts and blueprints, identified by its signal id.
+///
+/// Ids below 1000 are plain signals, items keep their own ids, recipes start at 20000 and
+/// technologies at 40000.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Copy, Clone, Default)]
+pub enum Signal {
+    #[default]
+    None,
+    Signal(i32),
+    Item(i32),
+    Recipe(i32),
+    Tech(i32),
+    Unknown(i32),
+}
+
+impl Signal {
+    const ITEMS: i32 = 1000;
+    const RECIPES: i32 = 20000;
+    const TECHS: i32 = 40000;
+    const END: i32 = 60000;
+
+    pub fn from_id(id: i32) -> Self {
+        match id {
+            0 => Signal::None,
+            1..Self::ITEMS => Signal::Signal(id),
+            Self::ITEMS..Self::RECIPES => Signal::Item(id),
+            Self::RECIPES..Self::TECHS => Signal::Recipe(id - Self::RECIPES),
+            Self::TECHS..Self::END => Signal::Tech(id - Self::TECHS),
+            _ => Signal::Unknown(id),
+        }
+    }
+
+    pub fn id(&self) -> i32 {
+        match self {
+            Signal::None => 0,
+            Signal::Signal(id) | Signal::Item(id) | Signal::Unknown(id) => *id,
+            Signal::Recipe(id) => id + Self::RECIPES,
+            Signal::Tech(id) => id + Self::TECHS,
+        }
+    }
+
+    /// Item of an item icon, `None` for other icons and unknown items.
+    pub fn item(&self) -> Option<DysonSphereItem> {
+        match self {
+            Signal::Item(id) => DysonSphereItem::try_from(*id).ok(),
+            _ => None,
+        }
+    }
+
+    /// Recipe of a recipe icon, `None` for other icons and unknown recipes.
+    pub fn recipe(&self) -> Option<Recipe> {
+        match self {
+            Signal::Recipe(id) => Recipe::try_from(*id).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl From<DysonSphereItem> for Signal {
+    fn from(item: DysonSphereItem) -> Self {
+        Signal::from_id(item as i32)
+    }
+}
+
+impl From<Recipe> for Signal {
+    fn from(recipe: Recipe) -> Self {
+        Signal::Recipe(recipe as i32)
+    }
+}
@@ -3,61 +3,302 @@
  * All rights reserved.
  */
 
-use serde::Serializer;
+use crate::md5hash::MD5NotFinalized;
+use std::fmt;
 
-#[derive(Debug)]
-pub struct InvalidDataCount(pub usize);
+/// Part of a blueprint an error was found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Section {
+    /// The `BLUEPRINT:` string with its header components, payload and hash.
+    #[default]
+    Header,
+    /// Fixed-size header of the decoded binary data.
+    DataHeader,
+    Areas,
+    BuildingHeader,
+    Buildings,
+}
 
-impl std::fmt::Display for InvalidDataCount {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.serialize_newtype_struct("invalid_data_count", &self.0)
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Section::Header => "blueprint header",
+            Section::DataHeader => "data header",
+            Section::Areas => "areas",
+            Section::BuildingHeader => "building header",
+            Section::Buildings => "buildings",
+        };
+        f.write_str(name)
     }
 }
 
-impl std::error::Error for InvalidDataCount {}
-
-#[derive(Debug)]
-pub struct CorruptedData;
+/// Position within the decoded blueprint data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Location {
+    pub section: Section,
+    /// Byte offset within the decoded data.
+    pub offset: usize,
+    /// Position of the building being decoded within the building list.
+    pub building: Option<usize>,
+}
 
-impl std::fmt::Display for CorruptedData {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.serialize_unit_struct("corrupted_data")
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.section)?;
+        if let Some(building) = self.building {
+            write!(f, " (building #{})", building)?;
+        }
+        write!(f, " at offset {}", self.offset)
     }
 }
 
-impl std::error::Error for CorruptedData {}
-
+/// Every error produced while reading, validating or writing blueprints.
 #[derive(Debug)]
-pub struct UnknownDysonSphereItem(pub i32);
+pub enum BlueprintError {
+    /// The blueprint string doesn't follow `BLUEPRINT:0,layout,icons...,"payload"hash`.
+    HeaderFormat(String),
+    HashMismatch {
+        expected: String,
+        computed: String,
+    },
+    Base64(base64::DecodeError),
+    Gzip(std::io::Error),
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Digest(MD5NotFinalized),
+    /// The data ended before a value could be read.
+    TruncatedData {
+        location: Location,
+        needed: usize,
+        available: usize,
+    },
+    /// The data header holds values the game would reject.
+    CorruptedHeader {
+        location: Location,
+        reason: &'static str,
+    },
+    /// A value doesn't make sense where it was found.
+    CorruptedData {
+        location: Location,
+        reason: String,
+    },
+    UnknownItem {
+        id: i32,
+        location: Option<Location>,
+    },
+    UnknownBuildingType(i32),
+    UnknownIconLayout(i32),
+    UnknownRecipe(i32),
+    /// The parameters of a station are too short for its storage and slot layout.
+    StationLayout {
+        building: Option<usize>,
+        params_len: usize,
+        storage_len: usize,
+        slots_len: usize,
+    },
+    /// A collection is too large to be written in the binary format.
+    InvalidCount {
+        what: &'static str,
+        count: usize,
+    },
+    /// A building can't be written in the requested record version without dropping a field.
+    LossyBuildingVersion {
+        building_index: i32,
+        version: i32,
+        field: &'static str,
+    },
+    /// A building field disagrees with the same setting in its typed parameters.
+    ConflictingBuildingField {
+        building_index: i32,
+        field: &'static str,
+        value: i16,
+        parameters: i16,
+    },
+    /// A building position is past the end of the building list.
+    MissingBuilding {
+        position: usize,
+        building_count: usize,
+    },
+}
+
+impl BlueprintError {
+    /// Location in the data the error points to, if it has one.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            BlueprintError::TruncatedData { location, .. }
+            | BlueprintError::CorruptedHeader { location, .. }
+            | BlueprintError::CorruptedData { location, .. } => Some(*location),
+            BlueprintError::UnknownItem { location, .. } => *location,
+            _ => None,
+        }
+    }
 
-impl std::fmt::Display for UnknownDysonSphereItem {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.serialize_newtype_struct("unknown_dyson_sphere_item", &self.0)
+    /// Attaches the index of the building being decoded to errors which point into the data.
+    pub fn in_building(mut self, index: usize) -> Self {
+        match &mut self {
+            BlueprintError::TruncatedData { location, .. }
+            | BlueprintError::CorruptedHeader { location, .. }
+            | BlueprintError::CorruptedData { location, .. }
+            | BlueprintError::UnknownItem {
+                location: Some(location),
+                ..
+            } => location.building = Some(index),
+            BlueprintError::StationLayout { building, .. } => *building = Some(index),
+            _ => {}
+        }
+        self
     }
 }
 
-impl std::error::Error for UnknownDysonSphereItem {}
+impl fmt::Display for BlueprintError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BlueprintError::HeaderFormat(message) => {
+                write!(f, "invalid blueprint string: {}", message)
+            }
+            BlueprintError::HashMismatch { expected, computed } => write!(
+                f,
+                "blueprint hash mismatch: expected {}, computed {}",
+                expected, computed
+            ),
+            BlueprintError::Base64(e) => write!(f, "invalid base64 payload: {}", e),
+            BlueprintError::Gzip(e) => write!(f, "invalid gzip payload: {}", e),
+            BlueprintError::Io(e) => write!(f, "i/o error: {}", e),
+            BlueprintError::Json(e) => write!(f, "json error: {}", e),
+            BlueprintError::Digest(e) => write!(f, "digest error: {}", e),
+            BlueprintError::TruncatedData {
+                location,
+                needed,
+                available,
+            } => write!(
+                f,
+                "truncated data in {}: needed {} bytes, {} available",
+                location, needed, available
+            ),
+            BlueprintError::CorruptedHeader { location, reason } => {
+                write!(f, "corrupted {}: {}", location, reason)
+            }
+            BlueprintError::CorruptedData { location, reason } => {
+                write!(f, "corrupted {}: {}", location, reason)
+            }
+            BlueprintError::UnknownItem { id, location } => {
+                write!(f, "unknown item {}", id)?;
+                match location {
+                    Some(location) => write!(f, " in {}", location),
+                    None => Ok(()),
+                }
+            }
+            BlueprintError::UnknownBuildingType(value) => {
+                write!(f, "unknown building type {}", value)
+            }
+            BlueprintError::UnknownIconLayout(value) => write!(f, "unknown icon layout {}", value),
+            BlueprintError::UnknownRecipe(value) => write!(f, "unknown recipe {}", value),
+            BlueprintError::StationLayout {
+                building,
+                params_len,
+                storage_len,
+                slots_len,
+            } => {
+                write!(
+                    f,
+                    "{} station parameters don't fit {} storage and {} slots",
+                    params_len, storage_len, slots_len
+                )?;
+                match building {
+                    Some(building) => write!(f, " (building #{})", building),
+                    None => Ok(()),
+                }
+            }
+            BlueprintError::InvalidCount { what, count } => {
+                write!(f, "too many {} to serialize: {}", what, count)
+            }
+            BlueprintError::LossyBuildingVersion {
+                building_index,
+                version,
+                field,
+            } => write!(
+                f,
+                "building {} can't be written as version {} without losing `{}`",
+                building_index, version, field
+            ),
+            BlueprintError::ConflictingBuildingField {
+                building_index,
+                field,
+                value,
+                parameters,
+            } => write!(
+                f,
+                "building {} has `{}` {} but its parameters set it to {}",
+                building_index, field, value, parameters
+            ),
+            BlueprintError::MissingBuilding {
+                position,
+                building_count,
+            } => write!(
+                f,
+                "there's no building #{}, the blueprint has {} buildings",
+                position, building_count
+            ),
+        }
+    }
+}
 
-#[derive(Debug)]
-pub struct UnknownDysonSphereIconLayout(pub i32);
+impl std::error::Error for BlueprintError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BlueprintError::Base64(e) => Some(e),
+            BlueprintError::Gzip(e) | BlueprintError::Io(e) => Some(e),
+            BlueprintError::Json(e) => Some(e),
+            BlueprintError::Digest(e) => Some(e),
+            _ => None,
+        }
+    }
+}
 
-impl std::fmt::Display for UnknownDysonSphereIconLayout {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.serialize_newtype_struct("unknown_dyson_sphere_icon_layout", &self.0)
+impl From<base64::DecodeError> for BlueprintError {
+    fn from(e: base64::DecodeError) -> Self {
+        BlueprintError::Base64(e)
     }
 }
 
-impl std::error::Error for UnknownDysonSphereIconLayout {}
+impl From<std::io::Error> for BlueprintError {
+    fn from(e: std::io::Error) -> Self {
+        BlueprintError::Io(e)
+    }
+}
 
-#[derive(Debug)]
-pub struct UnknownDysonSphereBuildingType(pub i32);
+impl From<serde_json::Error> for BlueprintError {
+    fn from(e: serde_json::Error) -> Self {
+        BlueprintError::Json(e)
+    }
+}
 
-impl std::fmt::Display for UnknownDysonSphereBuildingType {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.serialize_newtype_struct("unknown_dyson_sphere_building_type", &self.0)
+impl From<MD5NotFinalized> for BlueprintError {
+    fn from(e: MD5NotFinalized) -> Self {
+        BlueprintError::Digest(e)
     }
 }
 
-impl std::error::Error for UnknownDysonSphereBuildingType {}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-// todo: maybe should be macro for all of these errors...
+    #[test]
+    fn test_display_with_location() {
+        let err = BlueprintError::TruncatedData {
+            location: Location {
+                section: Section::Buildings,
+                offset: 120,
+                building: None,
+            },
+            needed: 4,
+            available: 2,
+        }
+        .in_building(3);
+        assert_eq!(err.location().unwrap().building, Some(3));
+        assert_eq!(
+            err.to_string(),
+            "truncated data in buildings (building #3) at offset 120: needed 4 bytes, 2 available"
+        );
+    }
+}
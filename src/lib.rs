@@ -0,0 +1,73 @@
+/*
+ * Copyright (c) 2025. sakost aka Konstantin Sazhenov
+ * All rights reserved.
+ */
+
+//! Dyson Sphere Program blueprint parser.
+//!
+//! The public types are re-exported at the crate root, the most commonly used ones are also
+//! gathered in [`prelude`]:
+//!
+//! ```no_run
+//! use dsp_bp::prelude::*;
+//!
+//! let bp = Blueprint::read_from_file("bp.txt", true)?;
+//! for building in bp.decoded_data()?.buildings.iter() {
+//!     if let BlueprintBuildingParameters::Station(station) = building.get_parameters() {
+//!         println!("station with {} storage slots", station.storage.len());
+//!     }
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+pub(crate) mod csharptime;
+pub(crate) mod entities;
+pub(crate) mod errors;
+pub(crate) mod md5hash;
+pub(crate) mod parsers;
+
+pub use entities::recipe::RecipeClass;
+pub use errors::{Location, Section};
+pub use md5hash::MD5NotFinalized;
+pub use parsers::factory_bp::assembler::ProliferatorMode;
+pub use parsers::factory_bp::lab::LabMode;
+pub use parsers::factory_bp::power::{ExchangerMode, RayReceiverMode};
+pub use parsers::factory_bp::sorter::{SorterConnection, SorterEnd};
+pub use parsers::factory_bp::splitter::SplitterSide;
+pub use parsers::factory_bp::station::{
+    LogisticsStationDirection, RoutePriority, SlotEntry, StationKind, StationSettings,
+    StorageEntry, StorageKeepMode, StorageLogic,
+};
+pub use prelude::*;
+
+/// Glob-importable set of the types needed for everyday blueprint handling.
+pub mod prelude {
+    pub use crate::csharptime::{CSharpTicks, DateTimeKind};
+    pub use crate::entities::building_types::BuildingType;
+    pub use crate::entities::icon_layout::IconLayout;
+    pub use crate::entities::item::DysonSphereItem;
+    pub use crate::entities::recipe::Recipe;
+    pub use crate::entities::signal::Signal;
+    pub use crate::errors::BlueprintError;
+    pub use crate::md5hash::{DysonSphereMD5, Variant};
+    pub use crate::parsers::factory_bp::area::BlueprintArea;
+    pub use crate::parsers::factory_bp::assembler::AssemblerParameters;
+    pub use crate::parsers::factory_bp::belt::BeltParameters;
+    pub use crate::parsers::factory_bp::blueprint::Blueprint;
+    pub use crate::parsers::factory_bp::building::{
+        BlueprintBuilding, BlueprintBuildingParameters, BuildingShape, BuildingVersion,
+    };
+    pub use crate::parsers::factory_bp::data::{BlueprintData, BlueprintDataHeader};
+    pub use crate::parsers::factory_bp::decode::{
+        DecodeFailure, DecodeMode, DecodeOptions, DecodeWarning, PartialDecode,
+    };
+    pub use crate::parsers::factory_bp::ejector::EjectorParameters;
+    pub use crate::parsers::factory_bp::lab::LabParameters;
+    pub use crate::parsers::factory_bp::power::{
+        ExchangerParameters, GeneratorParameters, RayReceiverParameters,
+    };
+    pub use crate::parsers::factory_bp::sorter::SorterParameters;
+    pub use crate::parsers::factory_bp::splitter::SplitterParameters;
+    pub use crate::parsers::factory_bp::station::StationParameters;
+    pub use crate::parsers::factory_bp::storage::{StorageParameters, TankParameters};
+}
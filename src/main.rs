@@ -3,50 +3,456 @@
  * All rights reserved.
  */
 
-#![allow(dead_code)]
-
-use crate::parsers::factory_bp::building::BlueprintBuildingParameters;
-use log::{debug, info};
-
-mod csharptime;
-mod entities;
-mod errors;
-mod md5hash;
-mod parsers;
-
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
-
-    let bp = parsers::factory_bp::blueprint::Blueprint::read_from_file("bp.txt", true)?;
-    for building in bp.decoded_data()?.buildings.iter() {
-        let params = building.get_parameters();
-        match params {
-            BlueprintBuildingParameters::Station(station_params) => {
-                info!(
-                    "handling {} station",
-                    if station_params.is_planetary() {
-                        "planetary"
-                    } else {
-                        "interstellar"
+use clap::{Args, Parser, Subcommand};
+use dsp_bp::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Some input isn't a valid blueprint or can't be processed.
+const EXIT_INVALID: u8 = 1;
+/// Bad command line, the same code clap uses.
+const EXIT_USAGE: u8 = 2;
+/// An input or output couldn't be read or written.
+const EXIT_IO: u8 = 3;
+
+/// Dyson Sphere Program blueprint tool.
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+struct Inputs {
+    /// Input files or directories of them, `-` or nothing reads stdin
+    inputs: Vec<PathBuf>,
+}
+
+#[derive(Args)]
+struct Outputs {
+    #[command(flatten)]
+    inputs: Inputs,
+    /// Output file, or directory for several inputs; stdout by default
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct Edits {
+    /// New short description
+    #[arg(long)]
+    short_desc: Option<String>,
+    /// New long description
+    #[arg(long)]
+    long_desc: Option<String>,
+    /// New game version
+    #[arg(long)]
+    game_version: Option<String>,
+    /// New timestamp in C# ticks
+    #[arg(long, conflicts_with = "touch")]
+    timestamp: Option<CSharpTicks>,
+    /// Set the timestamp to the current time
+    #[arg(long)]
+    touch: bool,
+    /// New icon layout
+    #[arg(long)]
+    icon_layout: Option<u32>,
+    /// New icons, five comma separated item IDs
+    #[arg(long, value_delimiter = ',')]
+    icons: Option<Vec<u32>>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Decode blueprint strings to JSON
+    Decode {
+        #[command(flatten)]
+        io: Outputs,
+        /// Don't check the hash of the blueprint strings
+        #[arg(long)]
+        no_validate: bool,
+        /// Keep building parameters as plain int arrays instead of typed ones
+        #[arg(long)]
+        raw_parameters: bool,
+    },
+    /// Encode JSON back to blueprint strings
+    Encode {
+        #[command(flatten)]
+        io: Outputs,
+    },
+    /// Print a summary of blueprints
+    Info {
+        #[command(flatten)]
+        io: Outputs,
+        /// Don't check the hash of the blueprint strings
+        #[arg(long)]
+        no_validate: bool,
+    },
+    /// Check the hash and the structure of blueprints
+    Validate {
+        #[command(flatten)]
+        inputs: Inputs,
+    },
+    /// Recompute the hash of blueprint strings
+    Rehash {
+        #[command(flatten)]
+        io: Outputs,
+    },
+    /// Change the header fields of blueprints
+    Edit {
+        #[command(flatten)]
+        io: Outputs,
+        #[command(flatten)]
+        edits: Edits,
+        /// Don't check the hash of the blueprint strings
+        #[arg(long)]
+        no_validate: bool,
+    },
+    /// Rewrite the building records of blueprints with another record version
+    Convert {
+        #[command(flatten)]
+        io: Outputs,
+        /// Record version: legacy, 100, 101 or 102
+        #[arg(long, value_parser = parse_building_version)]
+        building_version: BuildingVersion,
+        /// Don't check the hash of the blueprint strings
+        #[arg(long)]
+        no_validate: bool,
+    },
+}
+
+fn parse_building_version(value: &str) -> Result<BuildingVersion, String> {
+    match value {
+        "legacy" => Ok(BuildingVersion::Legacy),
+        "100" => Ok(BuildingVersion::V100),
+        "101" => Ok(BuildingVersion::V101),
+        "102" => Ok(BuildingVersion::V102),
+        _ => Err("expected legacy, 100, 101 or 102".to_string()),
+    }
+}
+
+#[derive(Debug)]
+enum Failure {
+    Blueprint(BlueprintError),
+    Io(io::Error),
+    Usage(String),
+}
+
+impl Failure {
+    fn exit_code(&self) -> u8 {
+        match self {
+            Failure::Blueprint(_) => EXIT_INVALID,
+            Failure::Usage(_) => EXIT_USAGE,
+            Failure::Io(_) => EXIT_IO,
+        }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Failure::Blueprint(e) => write!(f, "{}", e),
+            Failure::Io(e) => write!(f, "{}", e),
+            Failure::Usage(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl From<BlueprintError> for Failure {
+    fn from(e: BlueprintError) -> Self {
+        match e {
+            BlueprintError::Io(e) => Failure::Io(e),
+            e => Failure::Blueprint(e),
+        }
+    }
+}
+
+impl From<io::Error> for Failure {
+    fn from(e: io::Error) -> Self {
+        Failure::Io(e)
+    }
+}
+
+/// A file or stdin to process.
+struct Input {
+    path: Option<PathBuf>,
+}
+
+impl Input {
+    fn name(&self) -> String {
+        match &self.path {
+            Some(path) => path.display().to_string(),
+            None => "<stdin>".to_string(),
+        }
+    }
+
+    fn read(&self) -> Result<String, Failure> {
+        let mut contents = String::new();
+        match &self.path {
+            Some(path) => contents = fs::read_to_string(path)?,
+            None => {
+                io::stdin().read_to_string(&mut contents)?;
+            }
+        }
+        Ok(contents.trim().to_string())
+    }
+}
+
+/// Expands directories into the files in them, in name order.
+fn collect_inputs(inputs: &Inputs) -> Result<Vec<Input>, Failure> {
+    if inputs.inputs.is_empty() {
+        return Ok(vec![Input { path: None }]);
+    }
+    let mut collected = Vec::new();
+    for path in inputs.inputs.iter() {
+        if path.as_os_str() == "-" {
+            collected.push(Input { path: None });
+        } else if path.is_dir() {
+            let mut files = Vec::new();
+            for entry in fs::read_dir(path)? {
+                let entry = entry?;
+                if entry.file_type()?.is_file() {
+                    files.push(entry.path());
+                }
+            }
+            files.sort();
+            collected.extend(files.into_iter().map(|path| Input { path: Some(path) }));
+        } else {
+            collected.push(Input {
+                path: Some(path.clone()),
+            });
+        }
+    }
+    Ok(collected)
+}
+
+/// Where the results go.
+enum Output {
+    Stdout,
+    File(PathBuf),
+    Directory(PathBuf),
+}
+
+impl Output {
+    fn new(outputs: &Outputs, inputs: &[Input], extension: &str) -> Result<Self, Failure> {
+        match &outputs.output {
+            None => Ok(Output::Stdout),
+            Some(path) if inputs.len() > 1 || path.is_dir() => {
+                let mut names: BTreeMap<String, &Input> = BTreeMap::new();
+                for input in inputs.iter() {
+                    let name = Self::file_name(input, extension);
+                    if let Some(other) = names.insert(name.clone(), input) {
+                        return Err(Failure::Usage(format!(
+                            "{} and {} would both be written to {}",
+                            other.name(),
+                            input.name(),
+                            path.join(name).display()
+                        )));
                     }
-                );
-                for storage_entry in station_params.storage.iter() {
-                    debug!(
-                        "{} drone range for {}",
-                        station_params.parameters.drone_range,
-                        match storage_entry {
-                            None => "empty storage slot".to_string(),
-                            Some(storage_entry) => format!("{}", storage_entry.item()?),
-                        }
-                    );
                 }
+                fs::create_dir_all(path)?;
+                Ok(Output::Directory(path.clone()))
+            }
+            Some(path) => Ok(Output::File(path.clone())),
+        }
+    }
+
+    /// Name of the file the results for `input` get in an output directory.
+    fn file_name(input: &Input, extension: &str) -> String {
+        let stem = input
+            .path
+            .as_deref()
+            .and_then(Path::file_stem)
+            .map_or("stdin".into(), |stem| stem.to_string_lossy());
+        format!("{}.{}", stem, extension)
+    }
+
+    fn write(&self, input: &Input, contents: &str, extension: &str) -> Result<(), Failure> {
+        match self {
+            Output::Stdout => writeln!(io::stdout().lock(), "{}", contents)?,
+            Output::File(path) => fs::write(path, contents)?,
+            Output::Directory(dir) => {
+                fs::write(dir.join(Self::file_name(input, extension)), contents)?
             }
-            BlueprintBuildingParameters::Raw(_) => {
-                info!("Skipping unknown building");
-                continue;
+        }
+        Ok(())
+    }
+}
+
+/// Runs `process` on every input, writing its results to the output.
+fn run_each<F>(outputs: &Outputs, extension: &str, process: F) -> Result<u8, Failure>
+where
+    F: Fn(&str) -> Result<String, Failure>,
+{
+    let inputs = collect_inputs(&outputs.inputs)?;
+    let output = Output::new(outputs, &inputs, extension)?;
+    let mut exit_code = 0;
+    for input in inputs.iter() {
+        let result = input
+            .read()
+            .and_then(|text| process(&text))
+            .and_then(|contents| output.write(input, &contents, extension));
+        if let Err(e) = result {
+            eprintln!("{}: {}", input.name(), e);
+            exit_code = exit_code.max(e.exit_code());
+        }
+    }
+    Ok(exit_code)
+}
+
+fn parse(text: &str, validate_hash: bool) -> Result<Blueprint, Failure> {
+    Ok(Blueprint::from_blueprint_string(text, validate_hash)?)
+}
+
+fn info(bp: &Blueprint) -> Result<String, Failure> {
+    let (data, warnings) = bp.decoded_data_with(&DecodeOptions::lenient())?;
+    let mut counts: BTreeMap<i16, usize> = BTreeMap::new();
+    for building in data.buildings.iter() {
+        *counts.entry(building.item_id).or_default() += 1;
+    }
+    let mut lines = vec![
+        format!("short description: {}", bp.short_desc()),
+        format!("game version: {}", bp.game_version()),
+        format!("timestamp: {}", bp.timestamp().to_naive()),
+        format!("icon layout: {}", bp.layout()),
+        format!("icons: {:?}", bp.icons()),
+        format!("areas: {}", data.areas.len()),
+        format!("buildings: {}", data.buildings.len()),
+    ];
+    for (item_id, count) in counts {
+        let name = match DysonSphereItem::try_from(item_id as i32) {
+            Ok(item) => item.to_string(),
+            Err(_) => format!("unknown item {}", item_id),
+        };
+        lines.push(format!("  {} x{}", name, count));
+    }
+    lines.extend(warnings.iter().map(|w| format!("warning: {}", w)));
+    for i in data.sorter_mismatches() {
+        lines.push(format!(
+            "warning: sorter #{} isn't connected to matching buildings",
+            data.buildings[i].index
+        ));
+    }
+    for i in data.recipe_mismatches() {
+        let building = &data.buildings[i];
+        if let Ok(recipe) = Recipe::try_from(building.recipe_id as i32) {
+            lines.push(format!(
+                "warning: building #{} can't run recipe {}",
+                building.index, recipe
+            ));
+        }
+    }
+    for i in data.unknown_recipes() {
+        let building = &data.buildings[i];
+        lines.push(format!(
+            "note: building #{} runs recipe {}, which isn't known",
+            building.index, building.recipe_id
+        ));
+    }
+    Ok(lines.join("\n"))
+}
+
+fn validate(inputs: &Inputs) -> Result<u8, Failure> {
+    let mut exit_code = 0;
+    for input in collect_inputs(inputs)? {
+        let result = input.read().and_then(|text| {
+            let bp = parse(&text, true)?;
+            IconLayout::try_from(bp.layout() as i32)?;
+            bp.decoded_data()?;
+            Ok(())
+        });
+        match result {
+            Ok(()) => println!("{}: valid", input.name()),
+            Err(e) => {
+                eprintln!("{}: {}", input.name(), e);
+                exit_code = exit_code.max(e.exit_code());
             }
         }
     }
-    println!("{}", bp.to_json()?);
+    Ok(exit_code)
+}
+
+fn edit(bp: &mut Blueprint, edits: &Edits) -> Result<(), Failure> {
+    if let Some(short_desc) = &edits.short_desc {
+        bp.set_short_desc(short_desc.clone());
+    }
+    if let Some(long_desc) = &edits.long_desc {
+        bp.set_long_desc(long_desc.clone());
+    }
+    if let Some(game_version) = &edits.game_version {
+        bp.set_game_version(game_version.clone());
+    }
+    if let Some(timestamp) = edits.timestamp {
+        bp.set_timestamp(timestamp);
+    }
+    if edits.touch {
+        bp.set_timestamp(CSharpTicks::now());
+    }
+    if let Some(layout) = edits.icon_layout {
+        bp.set_layout(layout);
+    }
+    if let Some(icons) = &edits.icons {
+        let icons = icons
+            .as_slice()
+            .try_into()
+            .map_err(|_| Failure::Usage(format!("expected 5 icons, found {}", icons.len())))?;
+        bp.set_icons(icons);
+    }
     Ok(())
 }
+
+fn run(command: Command) -> Result<u8, Failure> {
+    match command {
+        Command::Decode {
+            io,
+            no_validate,
+            raw_parameters,
+        } => run_each(&io, "json", |text| {
+            Ok(parse(text, !no_validate)?.to_json_with(raw_parameters)?)
+        }),
+        Command::Encode { io } => run_each(&io, "txt", |text| {
+            Ok(Blueprint::from_json(text)?.serialize()?)
+        }),
+        Command::Info { io, no_validate } => {
+            run_each(&io, "txt", |text| info(&parse(text, !no_validate)?))
+        }
+        Command::Validate { inputs } => validate(&inputs),
+        Command::Rehash { io } => run_each(&io, "txt", |text| Ok(parse(text, false)?.serialize()?)),
+        Command::Edit {
+            io,
+            edits,
+            no_validate,
+        } => run_each(&io, "txt", |text| {
+            let mut bp = parse(text, !no_validate)?;
+            edit(&mut bp, &edits)?;
+            Ok(bp.serialize()?)
+        }),
+        Command::Convert {
+            io,
+            building_version,
+            no_validate,
+        } => run_each(&io, "txt", |text| {
+            let mut bp = parse(text, !no_validate)?;
+            let data = bp.decoded_data()?;
+            bp.set_data_as(&data, building_version)?;
+            Ok(bp.serialize()?)
+        }),
+    }
+}
+
+fn main() -> ExitCode {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+
+    match run(Cli::parse().command) {
+        Ok(exit_code) => ExitCode::from(exit_code),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(e.exit_code())
+        }
+    }
+}
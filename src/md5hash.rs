@@ -21,7 +21,6 @@ struct RoundOp {
     d: usize,
     k: usize,
     s: u32,
-    i: u32,
     t: u32,
     op: fn(u32, u32, u32) -> u32,
 }
@@ -52,7 +51,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 3,
         k: 0,
         s: 7,
-        i: 1,
         t: 0xd76aa478,
         op: f,
     },
@@ -63,7 +61,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 2,
         k: 1,
         s: 12,
-        i: 2,
         t: 0xe8c7b756,
         op: f,
     },
@@ -74,7 +71,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 1,
         k: 2,
         s: 17,
-        i: 3,
         t: 0x242070db,
         op: f,
     },
@@ -85,7 +81,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 0,
         k: 3,
         s: 22,
-        i: 4,
         t: 0xc1bdceee,
         op: f,
     },
@@ -96,7 +91,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 3,
         k: 4,
         s: 7,
-        i: 5,
         t: 0xf57c0faf,
         op: f,
     },
@@ -107,7 +101,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 2,
         k: 5,
         s: 12,
-        i: 6,
         t: 0x4787c62a,
         op: f,
     },
@@ -118,7 +111,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 1,
         k: 6,
         s: 17,
-        i: 7,
         t: 0xa8304613,
         op: f,
     },
@@ -129,7 +121,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 0,
         k: 7,
         s: 22,
-        i: 8,
         t: 0xfd469501,
         op: f,
     },
@@ -140,7 +131,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 3,
         k: 8,
         s: 7,
-        i: 9,
         t: 0x698098d8,
         op: f,
     },
@@ -151,7 +141,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 2,
         k: 9,
         s: 12,
-        i: 10,
         t: 0x8b44f7af,
         op: f,
     },
@@ -162,7 +151,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 1,
         k: 10,
         s: 17,
-        i: 11,
         t: 0xffff5bb1,
         op: f,
     },
@@ -173,7 +161,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 0,
         k: 11,
         s: 22,
-        i: 12,
         t: 0x895cd7be,
         op: f,
     },
@@ -184,7 +171,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 3,
         k: 12,
         s: 7,
-        i: 13,
         t: 0x6b901122,
         op: f,
     },
@@ -195,7 +181,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 2,
         k: 13,
         s: 12,
-        i: 14,
         t: 0xfd987193,
         op: f,
     },
@@ -206,7 +191,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 1,
         k: 14,
         s: 17,
-        i: 15,
         t: 0xa679438e,
         op: f,
     },
@@ -217,7 +201,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 0,
         k: 15,
         s: 22,
-        i: 16,
         t: 0x49b40821,
         op: f,
     },
@@ -229,7 +212,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 3,
         k: 1,
         s: 5,
-        i: 17,
         t: 0xf61e2562,
         op: g,
     },
@@ -240,7 +222,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 2,
         k: 6,
         s: 9,
-        i: 18,
         t: 0xc040b340,
         op: g,
     },
@@ -251,7 +232,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 1,
         k: 11,
         s: 14,
-        i: 19,
         t: 0x265e5a51,
         op: g,
     },
@@ -262,7 +242,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 0,
         k: 0,
         s: 20,
-        i: 20,
         t: 0xe9b6c7aa,
         op: g,
     },
@@ -273,7 +252,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 3,
         k: 5,
         s: 5,
-        i: 21,
         t: 0xd62f105d,
         op: g,
     },
@@ -284,7 +262,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 2,
         k: 10,
         s: 9,
-        i: 22,
         t: 0x2441453,
         op: g,
     },
@@ -295,7 +272,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 1,
         k: 15,
         s: 14,
-        i: 23,
         t: 0xd8a1e681,
         op: g,
     },
@@ -306,7 +282,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 0,
         k: 4,
         s: 20,
-        i: 24,
         t: 0xe7d3fbc8,
         op: g,
     },
@@ -317,7 +292,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 3,
         k: 9,
         s: 5,
-        i: 25,
         t: 0x21e1cde6,
         op: g,
     },
@@ -328,7 +302,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 2,
         k: 14,
         s: 9,
-        i: 26,
         t: 0xc33707d6,
         op: g,
     },
@@ -339,7 +312,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 1,
         k: 3,
         s: 14,
-        i: 27,
         t: 0xf4d50d87,
         op: g,
     },
@@ -350,7 +322,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 0,
         k: 8,
         s: 20,
-        i: 28,
         t: 0x455a14ed,
         op: g,
     },
@@ -361,7 +332,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 3,
         k: 13,
         s: 5,
-        i: 29,
         t: 0xa9e3e905,
         op: g,
     },
@@ -372,7 +342,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 2,
         k: 2,
         s: 9,
-        i: 30,
         t: 0xfcefa3f8,
         op: g,
     },
@@ -383,7 +352,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 1,
         k: 7,
         s: 14,
-        i: 31,
         t: 0x676f02d9,
         op: g,
     },
@@ -394,7 +362,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 0,
         k: 12,
         s: 20,
-        i: 32,
         t: 0x8d2a4c8a,
         op: g,
     },
@@ -406,7 +373,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 3,
         k: 5,
         s: 4,
-        i: 33,
         t: 0xfffa3942,
         op: h,
     },
@@ -417,7 +383,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 2,
         k: 8,
         s: 11,
-        i: 34,
         t: 0x8771f681,
         op: h,
     },
@@ -428,7 +393,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 1,
         k: 11,
         s: 16,
-        i: 35,
         t: 0x6d9d6122,
         op: h,
     },
@@ -439,7 +403,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 0,
         k: 14,
         s: 23,
-        i: 36,
         t: 0xfde5380c,
         op: h,
     },
@@ -450,7 +413,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 3,
         k: 1,
         s: 4,
-        i: 37,
         t: 0xa4beea44,
         op: h,
     },
@@ -461,7 +423,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 2,
         k: 4,
         s: 11,
-        i: 38,
         t: 0x4bdecfa9,
         op: h,
     },
@@ -472,7 +433,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 1,
         k: 7,
         s: 16,
-        i: 39,
         t: 0xf6bb4b60,
         op: h,
     },
@@ -483,7 +443,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 0,
         k: 10,
         s: 23,
-        i: 40,
         t: 0xbebfbc70,
         op: h,
     },
@@ -494,7 +453,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 3,
         k: 13,
         s: 4,
-        i: 41,
         t: 0x289b7ec6,
         op: h,
     },
@@ -505,7 +463,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 2,
         k: 0,
         s: 11,
-        i: 42,
         t: 0xeaa127fa,
         op: h,
     },
@@ -516,7 +473,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 1,
         k: 3,
         s: 16,
-        i: 43,
         t: 0xd4ef3085,
         op: h,
     },
@@ -527,7 +483,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 0,
         k: 6,
         s: 23,
-        i: 44,
         t: 0x4881d05,
         op: h,
     },
@@ -538,7 +493,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 3,
         k: 9,
         s: 4,
-        i: 45,
         t: 0xd9d4d039,
         op: h,
     },
@@ -549,7 +503,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 2,
         k: 12,
         s: 11,
-        i: 46,
         t: 0xe6db99e5,
         op: h,
     },
@@ -560,7 +513,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 1,
         k: 15,
         s: 16,
-        i: 47,
         t: 0x1fa27cf8,
         op: h,
     },
@@ -571,7 +523,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 0,
         k: 2,
         s: 23,
-        i: 48,
         t: 0xc4ac5665,
         op: h,
     },
@@ -583,7 +534,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 3,
         k: 0,
         s: 6,
-        i: 49,
         t: 0xf4292244,
         op: i_func,
     },
@@ -594,7 +544,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 2,
         k: 7,
         s: 10,
-        i: 50,
         t: 0x432aff97,
         op: i_func,
     },
@@ -605,7 +554,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 1,
         k: 14,
         s: 15,
-        i: 51,
         t: 0xab9423a7,
         op: i_func,
     },
@@ -616,7 +564,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 0,
         k: 5,
         s: 21,
-        i: 52,
         t: 0xfc93a039,
         op: i_func,
     },
@@ -627,7 +574,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 3,
         k: 12,
         s: 6,
-        i: 53,
         t: 0x655b59c3,
         op: i_func,
     },
@@ -638,7 +584,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 2,
         k: 3,
         s: 10,
-        i: 54,
         t: 0x8f0ccc92,
         op: i_func,
     },
@@ -649,7 +594,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 1,
         k: 10,
         s: 15,
-        i: 55,
         t: 0xffeff47d,
         op: i_func,
     },
@@ -660,7 +604,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 0,
         k: 1,
         s: 21,
-        i: 56,
         t: 0x85845dd1,
         op: i_func,
     },
@@ -671,7 +614,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 3,
         k: 8,
         s: 6,
-        i: 57,
         t: 0x6fa87e4f,
         op: i_func,
     },
@@ -682,7 +624,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 2,
         k: 15,
         s: 10,
-        i: 58,
         t: 0xfe2ce6e0,
         op: i_func,
     },
@@ -693,7 +634,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 1,
         k: 6,
         s: 15,
-        i: 59,
         t: 0xa3014314,
         op: i_func,
     },
@@ -704,7 +644,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 0,
         k: 13,
         s: 21,
-        i: 60,
         t: 0x4e0811a1,
         op: i_func,
     },
@@ -715,7 +654,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 3,
         k: 4,
         s: 6,
-        i: 61,
         t: 0xf7537e82,
         op: i_func,
     },
@@ -726,7 +664,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 2,
         k: 11,
         s: 10,
-        i: 62,
         t: 0xbd3af235,
         op: i_func,
     },
@@ -737,7 +674,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 1,
         k: 2,
         s: 15,
-        i: 63,
         t: 0x2ad7d2bb,
         op: i_func,
     },
@@ -748,7 +684,6 @@ const BASE_ROUND_OPS: [RoundOp; 64] = [
         d: 0,
         k: 9,
         s: 21,
-        i: 64,
         t: 0xeb86d391,
         op: i_func,
     },
@@ -764,7 +699,6 @@ const MD5F_PATCHES: &[(usize, RoundOp)] = &[
             d: 2,
             k: 1,
             s: 12,
-            i: 2,
             t: 0xe8d7b756,
             op: f,
         },
@@ -778,7 +712,6 @@ const MD5F_PATCHES: &[(usize, RoundOp)] = &[
             d: 1,
             k: 6,
             s: 17,
-            i: 7,
             t: 0xa8304623,
             op: f,
         },
@@ -792,7 +725,6 @@ const MD5F_PATCHES: &[(usize, RoundOp)] = &[
             d: 3,
             k: 12,
             s: 7,
-            i: 13,
             t: 0x6b9f1122,
             op: f,
         },
@@ -806,7 +738,6 @@ const MD5F_PATCHES: &[(usize, RoundOp)] = &[
             d: 0,
             k: 15,
             s: 22,
-            i: 16,
             t: 0x39b40821,
             op: f,
         },
@@ -820,7 +751,6 @@ const MD5F_PATCHES: &[(usize, RoundOp)] = &[
             d: 0,
             k: 0,
             s: 20,
-            i: 20,
             t: 0xc9b6c7aa,
             op: g,
         },
@@ -834,7 +764,6 @@ const MD5F_PATCHES: &[(usize, RoundOp)] = &[
             d: 2,
             k: 10,
             s: 9,
-            i: 22,
             t: 0x2443453,
             op: g,
         },
@@ -848,7 +777,6 @@ const MD5F_PATCHES: &[(usize, RoundOp)] = &[
             d: 3,
             k: 9,
             s: 5,
-            i: 25,
             t: 0x21f1cde6,
             op: g,
         },
@@ -862,7 +790,6 @@ const MD5F_PATCHES: &[(usize, RoundOp)] = &[
             d: 0,
             k: 8,
             s: 20,
-            i: 28,
             t: 0x475a14ed,
             op: g,
         },
@@ -879,7 +806,6 @@ const MD5FC_PATCHES: &[(usize, RoundOp)] = &[
             d: 2,
             k: 1,
             s: 12,
-            i: 2,
             t: 0xe8d7b756,
             op: f,
         },
@@ -893,7 +819,6 @@ const MD5FC_PATCHES: &[(usize, RoundOp)] = &[
             d: 0,
             k: 3,
             s: 22,
-            i: 4,
             t: 0xc1bdceef,
             op: f,
         },
@@ -907,7 +832,6 @@ const MD5FC_PATCHES: &[(usize, RoundOp)] = &[
             d: 1,
             k: 6,
             s: 17,
-            i: 7,
             t: 0xa8304623,
             op: f,
         },
@@ -921,7 +845,6 @@ const MD5FC_PATCHES: &[(usize, RoundOp)] = &[
             d: 3,
             k: 12,
             s: 7,
-            i: 13,
             t: 0x6b9f1122,
             op: f,
         },
@@ -935,7 +858,6 @@ const MD5FC_PATCHES: &[(usize, RoundOp)] = &[
             d: 0,
             k: 15,
             s: 22,
-            i: 16,
             t: 0x39b40821,
             op: f,
         },
@@ -949,7 +871,6 @@ const MD5FC_PATCHES: &[(usize, RoundOp)] = &[
             d: 0,
             k: 0,
             s: 20,
-            i: 20,
             t: 0xc9b6c7aa,
             op: g,
         },
@@ -963,7 +884,6 @@ const MD5FC_PATCHES: &[(usize, RoundOp)] = &[
             d: 2,
             k: 10,
             s: 9,
-            i: 22,
             t: 0x2443453,
             op: g,
         },
@@ -977,7 +897,6 @@ const MD5FC_PATCHES: &[(usize, RoundOp)] = &[
             d: 3,
             k: 9,
             s: 5,
-            i: 25,
             t: 0x23f1cde6,
             op: g,
         },
@@ -991,7 +910,6 @@ const MD5FC_PATCHES: &[(usize, RoundOp)] = &[
             d: 0,
             k: 8,
             s: 20,
-            i: 28,
             t: 0x475a14ed,
             op: g,
         },
@@ -1005,7 +923,6 @@ const MD5FC_PATCHES: &[(usize, RoundOp)] = &[
             d: 1,
             k: 11,
             s: 16,
-            i: 35,
             t: 0x6d9d6121,
             op: h,
         },
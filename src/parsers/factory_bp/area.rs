@@ -3,8 +3,8 @@
  * All rights reserved.
  */
 
-use crate::errors::InvalidDataCount;
-use crate::parsers::factory_bp::utils::{read_i16, read_i8};
+use crate::errors::{BlueprintError, Section};
+use crate::parsers::factory_bp::utils::{write_i16, write_i8, BinaryReader};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -22,34 +22,37 @@ pub struct BlueprintArea {
 impl BlueprintArea {
     pub const SIZE: usize = 1 + 1 + 2 + 2 + 2 + 2 + 2 + 2; // 14 bytes
 
-    pub fn deserialize(
-        data: &[u8],
-        offset: usize,
-    ) -> Result<(Self, usize), Box<dyn std::error::Error>> {
-        if data.len() < BlueprintArea::SIZE + offset {
-            return Err(InvalidDataCount(data.len()).into());
-        }
-        let (index, offset) = read_i8(data, offset);
-        let (parent_index, offset) = read_i8(data, offset);
-        let (tropic_anchor, offset) = read_i16(data, offset);
-        let (area_segments, offset) = read_i16(data, offset);
-        let (anchor_local_offset_x, offset) = read_i16(data, offset);
-        let (anchor_local_offset_y, offset) = read_i16(data, offset);
-        let (width, offset) = read_i16(data, offset);
-        let (height, offset) = read_i16(data, offset);
-        Ok((
-            BlueprintArea {
-                index,
-                parent_index,
-                tropic_anchor,
-                area_segments,
-                anchor_local_offset_x,
-                anchor_local_offset_y,
-                width,
-                height,
-            },
-            offset,
-        ))
+    pub fn deserialize(data: &[u8], offset: usize) -> Result<(Self, usize), BlueprintError> {
+        let mut reader = BinaryReader::new(data, offset);
+        reader.set_section(Section::Areas);
+        let area = Self::read(&mut reader)?;
+        Ok((area, reader.offset()))
+    }
+
+    pub(crate) fn read(reader: &mut BinaryReader) -> Result<Self, BlueprintError> {
+        reader.ensure(BlueprintArea::SIZE)?;
+        Ok(BlueprintArea {
+            index: reader.read_i8()?,
+            parent_index: reader.read_i8()?,
+            tropic_anchor: reader.read_i16()?,
+            area_segments: reader.read_i16()?,
+            anchor_local_offset_x: reader.read_i16()?,
+            anchor_local_offset_y: reader.read_i16()?,
+            width: reader.read_i16()?,
+            height: reader.read_i16()?,
+        })
+    }
+
+    /// Appends the binary representation of the area to `data`.
+    pub fn serialize(&self, data: &mut Vec<u8>) {
+        write_i8(data, self.index);
+        write_i8(data, self.parent_index);
+        write_i16(data, self.tropic_anchor);
+        write_i16(data, self.area_segments);
+        write_i16(data, self.anchor_local_offset_x);
+        write_i16(data, self.anchor_local_offset_y);
+        write_i16(data, self.width);
+        write_i16(data, self.height);
     }
 
     pub fn to_json(&self) -> String {
@@ -82,5 +85,16 @@ mod tests {
         assert_eq!(area.anchor_local_offset_y, 0xDC);
         assert_eq!(area.width, 0x2211);
         assert_eq!(area.height, 0x4433);
+
+        let mut serialized = Vec::new();
+        area.serialize(&mut serialized);
+        assert_eq!(serialized, data);
+    }
+
+    #[test]
+    fn test_deserialize_truncated_area() {
+        let data = [0u8; BlueprintArea::SIZE];
+        assert!(BlueprintArea::deserialize(&data[..BlueprintArea::SIZE - 1], 0).is_err());
+        assert!(BlueprintArea::deserialize(&data, 1).is_err());
     }
 }
@@ -0,0 +1,101 @@
+/*
+ * Copyright (c) 2025. sakost aka Konstantin Sazhenov
+ * All rights reserved.
+ */
+
+use crate::entities::item::DysonSphereItem;
+use crate::entities::recipe::Recipe;
+use serde::{Deserialize, Serialize};
+
+/// What proliferated ingredients give.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Copy, Clone, Default)]
+pub enum ProliferatorMode {
+    #[default]
+    ExtraProducts,
+    ProductionSpeedup,
+}
+
+/// Settings of a building running recipes, kept in `parameters` and `recipe_id`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct AssemblerParameters {
+    /// Recipe the building runs, `0` if there's none.
+    pub recipe_id: i16,
+    pub proliferator_mode: ProliferatorMode,
+}
+
+impl AssemblerParameters {
+    /// Decodes the settings, missing parameters are taken as `0`.
+    pub fn new(params: &[i32], recipe_id: i16) -> Self {
+        let proliferator_mode = match params.first() {
+            Some(1) => ProliferatorMode::ProductionSpeedup,
+            _ => ProliferatorMode::ExtraProducts,
+        };
+        AssemblerParameters {
+            recipe_id,
+            proliferator_mode,
+        }
+    }
+
+    /// Recipe the building runs, `None` if there's none or it's unknown.
+    pub fn recipe(&self) -> Option<Recipe> {
+        Recipe::try_from(self.recipe_id as i32).ok()
+    }
+
+    /// Whether a building made of `item` can run the recipe, `true` if there's no recipe.
+    ///
+    /// Unknown recipes never fit, as nothing can be checked about them.
+    pub fn fits(&self, item: DysonSphereItem) -> bool {
+        self.recipe_id == 0 || self.recipe().is_some_and(|recipe| recipe.can_run_in(item))
+    }
+
+    /// Writes the settings into `params`, the recipe goes to `recipe_id` of the building.
+    ///
+    /// An empty array is only grown for the production speedup mode.
+    pub fn write(&self, params: &mut Vec<i32>) {
+        let mode = match self.proliferator_mode {
+            ProliferatorMode::ExtraProducts => 0,
+            ProliferatorMode::ProductionSpeedup => 1,
+        };
+        match params.first_mut() {
+            Some(param) => *param = mode,
+            None if mode != 0 => params.push(mode),
+            None => {}
+        }
+    }
+
+    /// Serializing to JSON-string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assembler() {
+        let assembler = AssemblerParameters::new(&[1], Recipe::Gear as i16);
+        assert_eq!(
+            assembler.proliferator_mode,
+            ProliferatorMode::ProductionSpeedup
+        );
+        assert_eq!(assembler.recipe(), Some(Recipe::Gear));
+        assert!(assembler.fits(DysonSphereItem::AssemblingMachineMkI));
+        assert!(!assembler.fits(DysonSphereItem::Smelter));
+        assert!(AssemblerParameters::new(&[], 0).fits(DysonSphereItem::Smelter));
+        assert!(!AssemblerParameters::new(&[], 105).fits(DysonSphereItem::AssemblingMachineMkI));
+
+        let mut params = vec![1, 5];
+        let mut changed = assembler.clone();
+        changed.proliferator_mode = ProliferatorMode::ExtraProducts;
+        changed.write(&mut params);
+        assert_eq!(params, [0, 5]);
+
+        let mut empty = Vec::new();
+        changed.write(&mut empty);
+        assert!(empty.is_empty());
+        assembler.write(&mut empty);
+        assert_eq!(empty, [1]);
+    }
+}
@@ -0,0 +1,70 @@
+/*
+ * Copyright (c) 2025. sakost aka Konstantin Sazhenov
+ * All rights reserved.
+ */
+
+use crate::entities::signal::Signal;
+use crate::parsers::factory_bp::utils::write_param;
+use serde::{Deserialize, Serialize};
+
+/// Label of a conveyor belt, kept in the first two parameters.
+///
+/// Belts without a label may have no parameters at all, missing ones are taken as `0`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct BeltParameters {
+    pub icon: Signal,
+    /// Number shown next to the icon.
+    pub number: i32,
+}
+
+impl BeltParameters {
+    const ICON: usize = 0;
+    const NUMBER: usize = 1;
+
+    pub fn new(params: &[i32]) -> Self {
+        let param = |i: usize| params.get(i).copied().unwrap_or(0);
+        BeltParameters {
+            icon: Signal::from_id(param(Self::ICON)),
+            number: param(Self::NUMBER),
+        }
+    }
+
+    /// Writes the label into `params`, which is only grown for a label that isn't empty.
+    pub fn write(&self, params: &mut Vec<i32>) {
+        write_param(params, Self::ICON, self.icon.id(), 0);
+        write_param(params, Self::NUMBER, self.number, 0);
+    }
+
+    /// Serializing to JSON-string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::item::DysonSphereItem;
+    use crate::entities::recipe::Recipe;
+
+    #[test]
+    fn test_label() {
+        let belt = BeltParameters::new(&[1101, 3]);
+        assert_eq!(belt.icon.item(), Some(DysonSphereItem::IronIngot));
+        assert_eq!(belt.number, 3);
+
+        let recipe = BeltParameters::new(&[20050]);
+        assert_eq!(recipe.icon.recipe(), Some(Recipe::CircuitBoard));
+        assert_eq!(recipe.number, 0);
+        assert_eq!(BeltParameters::new(&[401]).icon, Signal::Signal(401));
+        assert_eq!(BeltParameters::new(&[]), BeltParameters::default());
+
+        let mut params = Vec::new();
+        BeltParameters::default().write(&mut params);
+        assert!(params.is_empty());
+        recipe.write(&mut params);
+        assert_eq!(params, [20050]);
+        belt.write(&mut params);
+        assert_eq!(params, [1101, 3]);
+    }
+}
@@ -6,14 +6,18 @@
 use base64::Engine;
 use chrono::NaiveDateTime;
 use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::{Read, Write};
 use std::path::Path;
 use urlencoding::{decode, encode};
 
-use crate::csharptime;
+use crate::csharptime::{CSharpTicks, DateTimeKind};
+use crate::errors::BlueprintError;
 use crate::md5hash;
+use crate::parsers::factory_bp::building::BuildingVersion;
 use crate::parsers::factory_bp::data;
+use crate::parsers::factory_bp::decode::{DecodeOptions, DecodeWarning};
 
 #[derive(Debug, Clone)]
 pub struct Blueprint {
@@ -23,24 +27,16 @@ pub struct Blueprint {
     icon2: u32,
     icon3: u32,
     icon4: u32,
-    timestamp: NaiveDateTime,
+    timestamp: CSharpTicks,
     game_version: String,
     short_desc: String,
     long_desc: String,
     data: Vec<u8>,
+    /// Base64 payload the blueprint was parsed from, reused while `data` is unchanged because
+    /// the game compresses it differently.
+    payload: Option<String>,
 }
 
-/// Custom error type for hash validation errors.
-#[derive(Debug)]
-pub struct InvalidHashValueException(String);
-
-impl std::fmt::Display for InvalidHashValueException {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "InvalidHashValueException: {}", self.0)
-    }
-}
-impl std::error::Error for InvalidHashValueException {}
-
 impl Blueprint {
     /// Constructor.
     #[allow(clippy::too_many_arguments)]
@@ -53,12 +49,11 @@ impl Blueprint {
         icon2: u32,
         icon3: u32,
         icon4: u32,
-        timestamp: Option<NaiveDateTime>,
+        timestamp: Option<CSharpTicks>,
         short_desc: String,
         long_desc: String,
     ) -> Self {
-        let ts =
-            timestamp.unwrap_or_else(|| csharptime::csharp_to_datetime(csharptime::csharp_now()));
+        let ts = timestamp.unwrap_or_else(CSharpTicks::now);
         Blueprint {
             layout,
             icon0,
@@ -71,15 +66,43 @@ impl Blueprint {
             short_desc,
             long_desc,
             data,
+            payload: None,
         }
     }
 
+    /// Getter for icon layout.
+    pub fn layout(&self) -> u32 {
+        self.layout
+    }
+    /// Setter for icon layout.
+    pub fn set_layout(&mut self, value: u32) {
+        self.layout = value;
+    }
+
+    /// Getter for the five icons.
+    pub fn icons(&self) -> [u32; 5] {
+        [self.icon0, self.icon1, self.icon2, self.icon3, self.icon4]
+    }
+    /// Setter for the five icons.
+    pub fn set_icons(&mut self, value: [u32; 5]) {
+        [self.icon0, self.icon1, self.icon2, self.icon3, self.icon4] = value;
+    }
+
+    /// Getter for game version.
+    pub fn game_version(&self) -> &str {
+        &self.game_version
+    }
+    /// Setter for game version.
+    pub fn set_game_version(&mut self, value: String) {
+        self.game_version = value;
+    }
+
     /// Getter for timestamp.
-    pub fn timestamp(&self) -> NaiveDateTime {
+    pub fn timestamp(&self) -> CSharpTicks {
         self.timestamp
     }
     /// Setter for timestamp.
-    pub fn set_timestamp(&mut self, value: NaiveDateTime) {
+    pub fn set_timestamp(&mut self, value: CSharpTicks) {
         self.timestamp = value;
     }
 
@@ -102,10 +125,38 @@ impl Blueprint {
     }
 
     /// Returns deserialized blueprint data.
-    pub fn decoded_data(&self) -> Result<data::BlueprintData, Box<dyn std::error::Error>> {
+    pub fn decoded_data(&self) -> Result<data::BlueprintData, BlueprintError> {
         data::BlueprintData::deserialize(&self.data)
     }
 
+    /// Returns deserialized blueprint data decoded with `options`, along with the warnings.
+    pub fn decoded_data_with(
+        &self,
+        options: &DecodeOptions,
+    ) -> Result<(data::BlueprintData, Vec<DecodeWarning>), BlueprintError> {
+        data::BlueprintData::deserialize_with(&self.data, options)
+    }
+
+    /// Replaces the blueprint data with the serialized `data`.
+    pub fn set_data(&mut self, data: &data::BlueprintData) -> Result<(), BlueprintError> {
+        self.data = data.serialize()?;
+        self.payload = None;
+        Ok(())
+    }
+
+    /// Like [`Blueprint::set_data`], but writes every building with the `version` record layout.
+    ///
+    /// Fails if any of the buildings can't be written with `version` without losing data.
+    pub fn set_data_as(
+        &mut self,
+        data: &data::BlueprintData,
+        version: BuildingVersion,
+    ) -> Result<(), BlueprintError> {
+        self.data = data.serialize_as(version)?;
+        self.payload = None;
+        Ok(())
+    }
+
     /// Parses a blueprint string and returns a Blueprint instance.
     ///
     /// If `validate_hash` is true, computes the MD5 hash of the header portion and compares it
@@ -113,12 +164,12 @@ impl Blueprint {
     pub fn from_blueprint_string(
         bp_string: &str,
         validate_hash: bool,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
+    ) -> Result<Self, BlueprintError> {
         // Validate hash if requested.
         if validate_hash {
-            let index = bp_string
-                .rfind('"')
-                .ok_or("No double quote found for hash separation")?;
+            let index = bp_string.rfind('"').ok_or_else(|| {
+                BlueprintError::HeaderFormat("no double quote found before the hash".to_string())
+            })?;
             let hashed_data = &bp_string[..index];
             let ref_value = bp_string[index + 1..].trim().to_lowercase();
             let computed_hash = md5hash::DysonSphereMD5::new(md5hash::Variant::MD5F)
@@ -126,60 +177,69 @@ impl Blueprint {
                 .finalize()
                 .hexdigest()?;
             if ref_value != computed_hash {
-                return Err(Box::new(InvalidHashValueException(
-                    "Blueprint string has invalid hash value.".to_string(),
-                )));
+                return Err(BlueprintError::HashMismatch {
+                    expected: ref_value,
+                    computed: computed_hash,
+                });
             }
         }
 
         // Ensure string starts with "BLUEPRINT:".
         if !bp_string.starts_with("BLUEPRINT:") {
-            return Err("Blueprint string does not start with 'BLUEPRINT:'".into());
+            return Err(BlueprintError::HeaderFormat(
+                "string does not start with 'BLUEPRINT:'".to_string(),
+            ));
         }
         let content = &bp_string[10..]; // Remove prefix.
         let components: Vec<&str> = content.split(',').collect();
         if components.len() != 12 {
-            return Err("Invalid number of components in blueprint string".into());
+            return Err(BlueprintError::HeaderFormat(format!(
+                "expected 12 comma separated components, found {}",
+                components.len()
+            )));
         }
 
         // Unpack components.
-        let fixed0_1: i32 = components[0].parse()?;
-        let layout: u32 = components[1].parse()?;
-        let icon0: u32 = components[2].parse()?;
-        let icon1: u32 = components[3].parse()?;
-        let icon2: u32 = components[4].parse()?;
-        let icon3: u32 = components[5].parse()?;
-        let icon4: u32 = components[6].parse()?;
-        let fixed0_2: i32 = components[7].parse()?;
-        let timestamp_ticks: i64 = components[8].parse()?;
+        let fixed0_1: i32 = parse_component(components[0], "first fixed zero")?;
+        let layout: u32 = parse_component(components[1], "icon layout")?;
+        let icon0: u32 = parse_component(components[2], "icon 0")?;
+        let icon1: u32 = parse_component(components[3], "icon 1")?;
+        let icon2: u32 = parse_component(components[4], "icon 2")?;
+        let icon3: u32 = parse_component(components[5], "icon 3")?;
+        let icon4: u32 = parse_component(components[6], "icon 4")?;
+        let fixed0_2: i32 = parse_component(components[7], "second fixed zero")?;
+        let timestamp: CSharpTicks = parse_component(components[8], "timestamp")?;
         let game_version = components[9].to_string();
         let short_desc_enc = components[10];
         let b64data_hash = components[11];
 
         if fixed0_1 != 0 || fixed0_2 != 0 {
-            return Err("Fixed components are not zero".into());
+            return Err(BlueprintError::HeaderFormat(
+                "fixed components are not zero".to_string(),
+            ));
         }
 
-        let timestamp = csharptime::csharp_to_datetime(timestamp_ticks);
-        let short_desc = decode(short_desc_enc)?.into_owned();
+        let short_desc = decode_desc(short_desc_enc, "short description")?;
 
         // b64data_hash is expected to have three parts separated by double quotes.
         let parts: Vec<&str> = b64data_hash.split('"').collect();
         if parts.len() != 3 {
-            return Err("Invalid format for b64data_hash".into());
+            return Err(BlueprintError::HeaderFormat(
+                "expected long description, quoted payload and hash".to_string(),
+            ));
         }
         let long_desc_enc = parts[0];
         let b64data = parts[1];
         // The third part is the hash value (ignored here)
-        let long_desc = decode(long_desc_enc)?.into_owned();
+        let long_desc = decode_desc(long_desc_enc, "long description")?;
 
         // Decode base64 and decompress gzip.
         let compressed_data = base64::engine::general_purpose::STANDARD.decode(b64data)?;
         let mut gz = GzDecoder::new(&compressed_data[..]);
         let mut data = Vec::new();
-        gz.read_to_end(&mut data)?;
+        gz.read_to_end(&mut data).map_err(BlueprintError::Gzip)?;
 
-        Ok(Blueprint::new(
+        let mut blueprint = Blueprint::new(
             game_version,
             data,
             layout,
@@ -191,16 +251,27 @@ impl Blueprint {
             Some(timestamp),
             short_desc,
             long_desc,
-        ))
+        );
+        blueprint.payload = Some(b64data.to_string());
+        Ok(blueprint)
     }
 
     /// Serializes the blueprint into a blueprint string.
-    pub fn serialize(&self) -> Result<String, Box<dyn std::error::Error>> {
-        // Compress the data using gzip.
-        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
-        encoder.write_all(&self.data)?;
-        let compressed_data = encoder.finish()?;
-        let b64_data = base64::engine::general_purpose::STANDARD.encode(&compressed_data);
+    ///
+    /// A parsed blueprint with unchanged data is written back exactly as the game wrote it.
+    pub fn serialize(&self) -> Result<String, BlueprintError> {
+        let b64_data = match &self.payload {
+            Some(payload) => payload.clone(),
+            None => {
+                // Compress the data using gzip.
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder
+                    .write_all(&self.data)
+                    .map_err(BlueprintError::Gzip)?;
+                let compressed_data = encoder.finish().map_err(BlueprintError::Gzip)?;
+                base64::engine::general_purpose::STANDARD.encode(&compressed_data)
+            }
+        };
 
         // Build header components.
         let components = vec![
@@ -212,58 +283,166 @@ impl Blueprint {
             self.icon3.to_string(),
             self.icon4.to_string(),
             "0".to_string(),
-            csharptime::datetime_to_csharp(self.timestamp).to_string(),
+            self.timestamp.to_string(),
             self.game_version.clone(),
             encode(&self.short_desc).into_owned(),
         ];
         let header = format!("BLUEPRINT:{}", components.join(","));
-        let hashed_data = format!("{},\"{}", header, b64_data);
+        // the long description goes right before the quoted payload, the game writes it the
+        // same way as the short one
+        let hashed_data = format!("{},{}\"{}", header, encode(&self.long_desc), b64_data);
         let hash_value = md5hash::DysonSphereMD5::new(md5hash::Variant::MD5F)
             .update(hashed_data.as_bytes())
             .finalize()
-            .hexdigest()?;
+            .hexdigest()?
+            .to_uppercase();
         Ok(format!("{}\"{}", hashed_data, hash_value))
     }
 
     /// Returns a JSON representation of the blueprint.
-    pub fn to_json(&self) -> Result<String, Box<dyn std::error::Error>> {
-        use serde_json::json;
-        let dict = json!({
-            "icon": {
-                "layout": self.layout,
-                "images": [self.icon0, self.icon1, self.icon2, self.icon3, self.icon4],
+    ///
+    /// Building parameters are typed where their layout is known, see
+    /// [`BlueprintBuilding`](crate::BlueprintBuilding).
+    pub fn to_json(&self) -> Result<String, BlueprintError> {
+        self.to_json_with(false)
+    }
+
+    /// Like [`Blueprint::to_json`], but with `raw_parameters` every building keeps its plain
+    /// parameter array instead of the typed parameters.
+    pub fn to_json_with(&self, raw_parameters: bool) -> Result<String, BlueprintError> {
+        let data = self.decoded_data()?;
+        let raw: Vec<Vec<i32>> = match raw_parameters {
+            true => data
+                .buildings
+                .iter()
+                .map(|b| b.parameters.clone())
+                .collect(),
+            false => Vec::new(),
+        };
+        let document = BlueprintJson {
+            icon: IconJson {
+                layout: self.layout,
+                images: self.icons(),
             },
-            "timestamp": self.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
-            "game_version": self.game_version,
-            "short_desc": self.short_desc,
-            "data": serde_json::from_str::<data::BlueprintData>(&self.decoded_data()?.to_json())?
-        });
-        Ok(dict.to_string())
+            timestamp: self
+                .timestamp
+                .to_naive()
+                .format(TIMESTAMP_FORMAT)
+                .to_string(),
+            ticks: Some(self.timestamp),
+            game_version: self.game_version.clone(),
+            short_desc: self.short_desc.clone(),
+            long_desc: self.long_desc.clone(),
+            data,
+        };
+        let mut document = serde_json::to_value(&document)?;
+        if let Some(buildings) = document["data"]["buildings"].as_array_mut() {
+            for (building, raw) in buildings.iter_mut().zip(raw) {
+                let serde_json::Value::Object(building) = building else {
+                    continue;
+                };
+                building.remove("parameter_count");
+                building.remove("unmodeled_parameters");
+                building.insert("parameters".to_string(), raw.into());
+            }
+        }
+        Ok(document.to_string())
+    }
+
+    /// Creates a blueprint out of the JSON of [`Blueprint::to_json`].
+    ///
+    /// `ticks` takes precedence over `timestamp`, which is only used when the exact ticks are
+    /// missing, e.g. in hand-written JSON. It may have fractional seconds.
+    pub fn from_json(json: &str) -> Result<Self, BlueprintError> {
+        let document: BlueprintJson = serde_json::from_str(json)?;
+        let timestamp = match document.ticks {
+            Some(ticks) => ticks,
+            None => NaiveDateTime::parse_from_str(&document.timestamp, TIMESTAMP_FORMAT)
+                .ok()
+                .and_then(|dt| CSharpTicks::from_naive(dt, DateTimeKind::Unspecified))
+                .ok_or_else(|| {
+                    BlueprintError::HeaderFormat(format!(
+                        "invalid timestamp {:?}",
+                        document.timestamp
+                    ))
+                })?,
+        };
+        let [icon0, icon1, icon2, icon3, icon4] = document.icon.images;
+        Ok(Blueprint::new(
+            document.game_version,
+            document.data.serialize()?,
+            document.icon.layout,
+            icon0,
+            icon1,
+            icon2,
+            icon3,
+            icon4,
+            Some(timestamp),
+            document.short_desc,
+            document.long_desc,
+        ))
     }
 
     /// Reads a blueprint from a file.
     pub fn read_from_file<P: AsRef<Path>>(
         filename: P,
         validate_hash: bool,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
+    ) -> Result<Self, BlueprintError> {
         let contents = fs::read_to_string(filename)?;
         Blueprint::from_blueprint_string(&contents, validate_hash)
     }
 
     /// Writes the serialized blueprint string to a file.
-    pub fn write_to_file<P: AsRef<Path>>(
-        &self,
-        filename: P,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn write_to_file<P: AsRef<Path>>(&self, filename: P) -> Result<(), BlueprintError> {
         let serialized = self.serialize()?;
         fs::write(filename, serialized)?;
         Ok(())
     }
 }
 
+/// Format of the human-readable timestamp in JSON, `%.f` adds the fraction only when needed.
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+
+/// JSON document of [`Blueprint::to_json`] and [`Blueprint::from_json`].
+#[derive(Serialize, Deserialize)]
+struct BlueprintJson {
+    icon: IconJson,
+    timestamp: String,
+    /// Exact timestamp, the human-readable one doesn't keep the C# kind bits.
+    #[serde(default)]
+    ticks: Option<CSharpTicks>,
+    game_version: String,
+    short_desc: String,
+    #[serde(default)]
+    long_desc: String,
+    data: data::BlueprintData,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IconJson {
+    layout: u32,
+    images: [u32; 5],
+}
+
+fn parse_component<T: std::str::FromStr>(value: &str, name: &str) -> Result<T, BlueprintError>
+where
+    T::Err: std::fmt::Display,
+{
+    value
+        .parse()
+        .map_err(|e| BlueprintError::HeaderFormat(format!("invalid {} {:?}: {}", name, value, e)))
+}
+
+fn decode_desc(value: &str, name: &str) -> Result<String, BlueprintError> {
+    decode(value)
+        .map(|desc| desc.into_owned())
+        .map_err(|e| BlueprintError::HeaderFormat(format!("invalid {}: {}", name, e)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parsers::factory_bp::building::{BlueprintBuilding, BuildingVersion};
     use chrono::{NaiveDate, NaiveTime};
 
     fn dummy_bp_data() -> Vec<u8> {
@@ -305,11 +484,12 @@ mod tests {
             2,  // icon2
             3,  // icon3
             4,  // icon4
-            Some(
+            CSharpTicks::from_naive(
                 NaiveDate::from_ymd_opt(2020, 1, 1)
                     .unwrap()
                     .and_hms_opt(12, 0, 0)
                     .unwrap(),
+                DateTimeKind::Unspecified,
             ),
             "Short description".to_string(),
             "Long description".to_string(),
@@ -319,8 +499,7 @@ mod tests {
 
     #[test]
     fn test_parse_blueprint() {
-        let bp_raw_data = "BLUEPRINT:0,23,3009,609,0,0,0,0,638391476082347356,0.10.28.21014,BAB%20%28Filtered%20for%20Level%209%29,\"H4sIAAAAAAAAC2NkQAWMUAxh/2dgOAFlMsKFEWoPSG7Dxj7IfZTxPxQgVOeBSU50Q7AAXxZUHICGQ4DYD4gDoTgIikOh2B+Kw4A4HIg3QvEmKBZlheB6KA4TgeAIFgjeDMVirBBcCcXRLBCcAcXZUBwJxVugWJwVgmugOIEFgpOhOA6Ka1kh2GEOItzR8Vth0jAtAAAlI45WJAIAAA==\"09DCE7720CA8695F93D0C611DD833255";
-        let bp = Blueprint::from_blueprint_string(bp_raw_data, true).unwrap();
+        let bp = Blueprint::from_blueprint_string(GAME_STRING, true).unwrap();
 
         assert_eq!(bp.game_version, "0.10.28.21014");
         assert_eq!(bp.layout, 23);
@@ -329,11 +508,12 @@ mod tests {
         assert_eq!(bp.icon2, 0);
         assert_eq!(bp.icon3, 0);
         assert_eq!(bp.icon4, 0);
+        assert_eq!(bp.timestamp, CSharpTicks::from_raw(638391476082347356));
         assert_eq!(
-            bp.timestamp,
+            bp.timestamp.to_naive(),
             NaiveDateTime::new(
                 NaiveDate::from_ymd_opt(2023, 12, 26).unwrap(),
-                NaiveTime::from_hms_micro_opt(00, 33, 28, 234735).unwrap()
+                NaiveTime::from_hms_nano_opt(00, 33, 28, 234_735_600).unwrap()
             )
         );
         assert_eq!(bp.short_desc, "BAB (Filtered for Level 9)");
@@ -341,6 +521,30 @@ mod tests {
         assert_eq!(bp.data, dummy_bp_data(),);
     }
 
+    const GAME_STRING: &str = "BLUEPRINT:0,23,3009,609,0,0,0,0,638391476082347356,0.10.28.21014,BAB%20%28Filtered%20for%20Level%209%29,\"H4sIAAAAAAAAC2NkQAWMUAxh/2dgOAFlMsKFEWoPSG7Dxj7IfZTxPxQgVOeBSU50Q7AAXxZUHICGQ4DYD4gDoTgIikOh2B+Kw4A4HIg3QvEmKBZlheB6KA4TgeAIFgjeDMVirBBcCcXRLBCcAcXZUBwJxVugWJwVgmugOIEFgpOhOA6Ka1kh2GEOItzR8Vth0jAtAAAlI45WJAIAAA==\"09DCE7720CA8695F93D0C611DD833255";
+
+    #[test]
+    fn test_game_string_round_trip() {
+        let bp = Blueprint::from_blueprint_string(GAME_STRING, true).unwrap();
+        assert_eq!(bp.serialize().unwrap(), GAME_STRING);
+    }
+
+    #[test]
+    fn test_long_desc_round_trip() {
+        let mut bp = Blueprint::from_blueprint_string(GAME_STRING, true).unwrap();
+        let long_desc = "Two lines,\nwith \"quotes\", 100% ünïcode & a+b#";
+        bp.set_long_desc(long_desc.to_string());
+        let serialized = bp.serialize().unwrap();
+        // escaped like the short description, so the commas and quotes can't end the field
+        assert!(serialized.contains(
+            ",Two%20lines%2C%0Awith%20%22quotes%22%2C%20100%25%20%C3%BCn%C3%AFcode%20%26%20a%2Bb%23\"H4sI"
+        ));
+        let reparsed = Blueprint::from_blueprint_string(&serialized, true).unwrap();
+        assert_eq!(reparsed.long_desc(), long_desc);
+        assert_eq!(reparsed.serialize().unwrap(), serialized);
+        assert!(reparsed.to_json().unwrap().contains("ünïcode"));
+    }
+
     #[test]
     fn test_serialization_and_deserialization() {
         let bp_string = dummy_blueprint_string();
@@ -349,6 +553,7 @@ mod tests {
         assert_eq!(bp.layout, 10);
         assert_eq!(bp.icon1, 1);
         assert_eq!(bp.short_desc, "Short description");
+        assert_eq!(bp.long_desc, "Long description");
     }
 
     #[test]
@@ -361,15 +566,311 @@ mod tests {
         assert!(dict.contains("\"timestamp\""));
         assert!(dict.contains("\"game_version\""));
         assert!(dict.contains("\"short_desc\""));
+        assert!(dict.contains("\"long_desc\":\"Long description\""));
         assert!(dict.contains("\"data\""));
     }
 
+    #[test]
+    fn test_json_round_trip() {
+        let bp = Blueprint::from_blueprint_string(GAME_STRING, true).unwrap();
+        let json = bp.to_json().unwrap();
+        let imported = Blueprint::from_json(&json).unwrap();
+        assert_eq!(imported.to_json().unwrap(), json);
+        assert_eq!(imported.timestamp(), bp.timestamp());
+        assert_eq!(imported.icons(), bp.icons());
+        assert_eq!(imported.data, bp.data);
+
+        let reparsed =
+            Blueprint::from_blueprint_string(&imported.serialize().unwrap(), true).unwrap();
+        assert_eq!(reparsed.short_desc(), bp.short_desc());
+        assert_eq!(reparsed.decoded_data().unwrap(), bp.decoded_data().unwrap());
+    }
+
+    #[test]
+    fn test_json_raw_parameters() {
+        use crate::entities::item::DysonSphereItem;
+
+        let mut bp = Blueprint::from_blueprint_string(GAME_STRING, true).unwrap();
+        let mut decoded = bp.decoded_data().unwrap();
+        let station = &mut decoded.buildings[0];
+        station.item_id = DysonSphereItem::PlanetaryLogisticsStation as i16;
+        station.version = BuildingVersion::V102;
+        station.parameters = (0..2048).map(|i| i % 3).collect();
+        bp.set_data(&decoded).unwrap();
+
+        let typed: serde_json::Value = serde_json::from_str(&bp.to_json().unwrap()).unwrap();
+        let building = &typed["data"]["buildings"][0];
+        assert!(building["parameters"]["Station"].is_object());
+        assert_eq!(building["parameter_count"], 2048);
+
+        let raw: serde_json::Value = serde_json::from_str(&bp.to_json_with(true).unwrap()).unwrap();
+        let building = &raw["data"]["buildings"][0];
+        assert_eq!(building["parameters"].as_array().unwrap().len(), 2048);
+        assert!(building.get("parameter_count").is_none());
+
+        for json in [typed, raw] {
+            let imported = Blueprint::from_json(&json.to_string()).unwrap();
+            assert_eq!(imported.decoded_data().unwrap(), decoded);
+        }
+    }
+
+    #[test]
+    fn test_json_without_ticks() {
+        let bp = Blueprint::from_blueprint_string(GAME_STRING, true).unwrap();
+        let mut document: serde_json::Value = serde_json::from_str(&bp.to_json().unwrap()).unwrap();
+        assert_eq!(document["timestamp"], "2023-12-26 00:33:28.234735600");
+        let object = document.as_object_mut().unwrap();
+        object.remove("ticks");
+        object.remove("long_desc");
+        let imported = Blueprint::from_json(&document.to_string()).unwrap();
+        assert_eq!(imported.timestamp(), bp.timestamp());
+        assert_eq!(imported.long_desc(), "");
+
+        document["timestamp"] = "2020-01-01 12:00:00".into();
+        let imported = Blueprint::from_json(&document.to_string()).unwrap();
+        assert_eq!(
+            imported.timestamp().to_naive(),
+            NaiveDate::from_ymd_opt(2020, 1, 1)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap()
+        );
+
+        document["timestamp"] = "yesterday".into();
+        assert!(matches!(
+            Blueprint::from_json(&document.to_string()),
+            Err(BlueprintError::HeaderFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_data_round_trip() {
+        let decoded = data::BlueprintData::deserialize(&dummy_bp_data()).unwrap();
+        let serialized = decoded.serialize().unwrap();
+        assert_eq!(serialized.len(), decoded.size());
+        assert_eq!(serialized, dummy_bp_data());
+    }
+
+    #[test]
+    fn test_data_version_conversion() {
+        let decoded = data::BlueprintData::deserialize(&dummy_bp_data()).unwrap();
+        assert_eq!(decoded.buildings[0].version, BuildingVersion::Legacy);
+
+        // the building of the fixture has an unknown item, so only item independent layouts work
+        let upgraded = decoded.serialize_as(BuildingVersion::V100).unwrap();
+        assert_eq!(upgraded.len(), dummy_bp_data().len() + 4 + 4);
+        let reparsed = data::BlueprintData::deserialize(&upgraded).unwrap();
+        assert_eq!(reparsed.buildings[0].version, BuildingVersion::V100);
+        assert_eq!(
+            reparsed.serialize_as(BuildingVersion::Legacy).unwrap(),
+            dummy_bp_data()
+        );
+        assert!(decoded.serialize_as(BuildingVersion::V102).is_err());
+    }
+
+    #[test]
+    fn test_set_data() {
+        let bp_string = dummy_blueprint_string();
+        let mut bp = Blueprint::from_blueprint_string(&bp_string, true).unwrap();
+        let mut decoded = bp.decoded_data().unwrap();
+        decoded.buildings[0].local_offset_x += 1.0;
+        bp.set_data(&decoded).unwrap();
+
+        let reparsed = Blueprint::from_blueprint_string(&bp.serialize().unwrap(), true).unwrap();
+        assert_eq!(reparsed.decoded_data().unwrap(), decoded);
+    }
+
+    #[test]
+    fn test_malformed_data_does_not_panic() {
+        let data = dummy_bp_data();
+        for len in 0..data.len() {
+            assert!(data::BlueprintData::deserialize(&data[..len]).is_err());
+        }
+
+        // flip bytes with a small deterministic generator to hit every branch of the decoder
+        let mut state: u32 = 0x1234_5678;
+        for _ in 0..5000 {
+            let mut mutated = data.clone();
+            for _ in 0..4 {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                let position = (state >> 8) as usize % mutated.len();
+                mutated[position] = (state >> 24) as u8;
+            }
+            let _ = data::BlueprintData::deserialize(&mutated);
+        }
+
+        for building_record in [
+            &[0x9a, 0xff, 0xff, 0xff, 0, 0, 0, 0, 0xd1, 0x07][..],
+            &[0x9b, 0xff, 0xff, 0xff, 0, 0, 0, 0, 0xeb, 0x07][..],
+            &[0x9c, 0xff, 0xff, 0xff][..],
+        ] {
+            let mut truncated = data[..47].to_vec();
+            truncated.extend_from_slice(building_record);
+            assert!(data::BlueprintData::deserialize(&truncated).is_err());
+        }
+    }
+
+    #[test]
+    fn test_error_locations() {
+        use crate::errors::Section;
+
+        let data = dummy_bp_data();
+        match data::BlueprintData::deserialize(&data[..100]).unwrap_err() {
+            BlueprintError::TruncatedData { location, .. } => {
+                assert_eq!(location.section, Section::Buildings);
+                assert_eq!(location.building, Some(0));
+                assert_eq!(location.offset, 100);
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+
+        let mut corrupted = data.clone();
+        corrupted[28] = 0x80; // area count
+        match data::BlueprintData::deserialize(&corrupted).unwrap_err() {
+            BlueprintError::CorruptedHeader { location, .. } => {
+                assert_eq!(location.section, Section::DataHeader);
+                assert_eq!(location.offset, 28);
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+
+        // one past the only area
+        let mut corrupted = data.clone();
+        corrupted[24] = 1; // primary area index
+        match data::BlueprintData::deserialize(&corrupted).unwrap_err() {
+            BlueprintError::CorruptedHeader { location, .. } => {
+                assert_eq!(location.section, Section::DataHeader);
+                assert_eq!(location.offset, 24);
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+
+        // turn the legacy record into a -102 one with the unknown item 3009
+        let mut unknown_item = data[..47].to_vec();
+        unknown_item.extend_from_slice(&(-102i32).to_le_bytes());
+        unknown_item.extend_from_slice(&0i32.to_le_bytes());
+        unknown_item.extend_from_slice(&3009i16.to_le_bytes());
+        unknown_item.extend_from_slice(&[0; 64]);
+        match data::BlueprintData::deserialize(&unknown_item).unwrap_err() {
+            BlueprintError::UnknownItem { id, location } => {
+                assert_eq!(id, 3009);
+                let location = location.unwrap();
+                assert_eq!(location.building, Some(0));
+                assert_eq!(location.offset, 47 + 8);
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_lenient_unknown_item() {
+        use crate::parsers::factory_bp::building::BuildingShape;
+
+        // the fixture building as a -102 record, 3009 isn't a known item
+        let mut decoded = data::BlueprintData::deserialize(&dummy_bp_data()).unwrap();
+        decoded.buildings[0].version = BuildingVersion::V102;
+        decoded.buildings[0].shape = Some(BuildingShape::Other);
+        let upgraded = decoded.serialize().unwrap();
+
+        assert!(matches!(
+            data::BlueprintData::deserialize(&upgraded),
+            Err(BlueprintError::UnknownItem { id: 3009, .. })
+        ));
+
+        let (lenient, warnings) =
+            data::BlueprintData::deserialize_with(&upgraded, &DecodeOptions::lenient()).unwrap();
+        assert_eq!(lenient, decoded);
+        assert_eq!(warnings.len(), 1);
+        match &warnings[0] {
+            DecodeWarning::UnknownItem {
+                id,
+                location,
+                shape,
+            } => {
+                assert_eq!(*id, 3009);
+                assert_eq!(location.building, Some(0));
+                assert_eq!(*shape, BuildingShape::Other);
+            }
+        }
+        assert_eq!(lenient.serialize().unwrap(), upgraded);
+    }
+
+    #[test]
+    fn test_partial_decode() {
+        let mut decoded = data::BlueprintData::deserialize(&dummy_bp_data()).unwrap();
+        let mut building = decoded.buildings.remove(0);
+        building.item_id = 2303;
+        building.version = BuildingVersion::V102;
+        for index in 0..3 {
+            decoded.buildings.push(BlueprintBuilding {
+                index,
+                ..building.clone()
+            });
+        }
+        let data = decoded.serialize().unwrap();
+        let second = 47 + building.size();
+        let third = second + building.size();
+
+        let partial =
+            data::BlueprintData::deserialize_partial(&data, &DecodeOptions::strict(), false)
+                .unwrap();
+        assert!(partial.is_complete());
+        assert_eq!(partial.data, decoded);
+
+        // a truncated blueprint keeps everything before the cut
+        let partial = data::BlueprintData::deserialize_partial(
+            &data[..third + 10],
+            &DecodeOptions::strict(),
+            true,
+        )
+        .unwrap();
+        assert_eq!(partial.data.buildings, decoded.buildings[..2]);
+        assert_eq!(partial.expected_buildings, 3);
+        let failure = partial.stopped_at().unwrap();
+        assert!(matches!(
+            failure.error,
+            BlueprintError::TruncatedData { .. }
+        ));
+        assert_eq!(failure.error.location().unwrap().building, Some(2));
+
+        // an unknown item in the second record
+        let mut corrupted = data.clone();
+        corrupted[second + 8..second + 10].copy_from_slice(&9999i16.to_le_bytes());
+        let partial =
+            data::BlueprintData::deserialize_partial(&corrupted, &DecodeOptions::strict(), false)
+                .unwrap();
+        assert_eq!(partial.data.buildings, decoded.buildings[..1]);
+        assert!(matches!(
+            partial.stopped_at().unwrap().error,
+            BlueprintError::UnknownItem { id: 9999, .. }
+        ));
+
+        let partial =
+            data::BlueprintData::deserialize_partial(&corrupted, &DecodeOptions::strict(), true)
+                .unwrap();
+        assert_eq!(partial.data.buildings.len(), 2);
+        assert_eq!(partial.data.buildings[0], decoded.buildings[0]);
+        assert_eq!(partial.data.buildings[1], decoded.buildings[2]);
+        assert!(partial.stopped_at().is_none());
+        assert_eq!(partial.failures.len(), 1);
+        assert_eq!(partial.failures[0].skipped, Some(second..third));
+        partial.data.serialize().unwrap();
+
+        // nothing can be recovered without the header
+        assert!(data::BlueprintData::deserialize_partial(
+            &data[..20],
+            &DecodeOptions::strict(),
+            true
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_invalid_hash() {
         let mut bp_string = dummy_blueprint_string();
         // Tamper with the blueprint string so that the hash becomes invalid.
         bp_string.push_str("tamper");
         let res = Blueprint::from_blueprint_string(&bp_string, true);
-        assert!(res.is_err());
+        assert!(matches!(res, Err(BlueprintError::HashMismatch { .. })));
     }
 }
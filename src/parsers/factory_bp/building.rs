@@ -6,11 +6,28 @@
 #![allow(unexpected_cfgs)]
 
 use crate::entities::item::DysonSphereItem;
-use crate::parsers::factory_bp::station::StationParameters;
-use crate::parsers::factory_bp::utils::{read_f32, read_i16, read_i32, read_i8};
+use crate::entities::recipe::RecipeClass;
+use crate::errors::{BlueprintError, Section};
+use crate::parsers::factory_bp::assembler::AssemblerParameters;
+use crate::parsers::factory_bp::belt::BeltParameters;
+use crate::parsers::factory_bp::decode::{DecodeOptions, DecodeWarning};
+use crate::parsers::factory_bp::ejector::EjectorParameters;
+use crate::parsers::factory_bp::json::BuildingJson;
+use crate::parsers::factory_bp::lab::LabParameters;
+use crate::parsers::factory_bp::power::{
+    ExchangerParameters, GeneratorParameters, RayReceiverParameters,
+};
+use crate::parsers::factory_bp::sorter::SorterParameters;
+use crate::parsers::factory_bp::splitter::SplitterParameters;
+use crate::parsers::factory_bp::station::{StationKind, StationParameters};
+use crate::parsers::factory_bp::storage::{StorageParameters, TankParameters};
+use crate::parsers::factory_bp::utils::{write_f32, write_i16, write_i32, write_i8, BinaryReader};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+/// In serde `parameters` holds the typed [`BlueprintBuilding::get_parameters`], along with
+/// `parameter_count` and `unmodeled_parameters` to rebuild the exact array on import.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(into = "BuildingJson", try_from = "BuildingJson")]
 pub struct BlueprintBuilding {
     pub index: i32,
     pub area_index: i8,
@@ -40,21 +57,105 @@ pub struct BlueprintBuilding {
     pub pitch2: f32,
     pub parameters: Vec<i32>,
     pub content: String,
+    /// Record layout the building was read from and is written with by default.
+    #[serde(default)]
+    pub version: BuildingVersion,
+    /// Shape assumed while decoding an unknown item leniently, so it's written back the same way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shape: Option<BuildingShape>,
 }
 
 impl BlueprintBuilding {
-    pub(crate) fn size(&self) -> usize {
-        todo!("Implement actual calculation of BlueprintBuilding size")
+    /// Size of the smallest possible record: a legacy one without parameters.
+    pub(crate) const MIN_SIZE: usize = 4 + 1 + 4 * 8 + 2 * 2 + 4 * 2 + 6 + 2 * 2 + 2;
+
+    /// Building of `item` at the origin of area 0, with nothing connected and no parameters.
+    ///
+    /// `model_index` is left at `0`, it has to be set before the game can place the building.
+    pub fn new(index: i32, item: DysonSphereItem) -> Self {
+        BlueprintBuilding {
+            index,
+            area_index: 0,
+            local_offset_x: 0.0,
+            local_offset_y: 0.0,
+            local_offset_z: 0.0,
+            local_offset_x2: 0.0,
+            local_offset_y2: 0.0,
+            local_offset_z2: 0.0,
+            yaw: 0.0,
+            yaw2: 0.0,
+            item_id: item as i16,
+            model_index: 0,
+            output_object_index: -1,
+            input_object_index: -1,
+            output_to_slot: 0,
+            input_from_slot: 0,
+            output_from_slot: 0,
+            input_to_slot: 0,
+            output_offset: 0,
+            input_offset: 0,
+            recipe_id: 0,
+            filter_id: 0,
+            tilt: 0.0,
+            tilt2: 0.0,
+            pitch: 0.0,
+            pitch2: 0.0,
+            parameters: Vec::new(),
+            content: String::new(),
+            version: BuildingVersion::default(),
+            shape: None,
+        }
+    }
+
+    /// Size in bytes of the building record written by [`BlueprintBuilding::serialize`].
+    pub fn size(&self) -> usize {
+        self.size_as(self.version)
+    }
+
+    /// Size in bytes of the building record written with the `version` layout.
+    pub fn size_as(&self, version: BuildingVersion) -> usize {
+        let shape = self.shape();
+        let positions = match version {
+            BuildingVersion::Legacy => 4 * 3 * 2 + 4 * 2,
+            BuildingVersion::V100 => 4 * 3 * 2 + 4 * 3,
+            BuildingVersion::V101 => match shape {
+                BuildingShape::Belt => 4 * 4 + 4,
+                BuildingShape::Sorter => 4 * 4 + 4 * 2 + 4 * 3 + 4 * 3,
+                BuildingShape::Other => 4 * 4,
+            },
+            BuildingVersion::V102 => match shape {
+                BuildingShape::Belt => 4 * 4 + 4 + 4 * 3,
+                BuildingShape::Sorter => 4 * 4 + 4 * 2 + 4 * 3 + 4 * 3,
+                BuildingShape::Other => 4 * 4 + 4 * 3,
+            },
+        };
+        let header = match version {
+            BuildingVersion::Legacy => 4 + 1 + 2 + 2,
+            _ => 4 + 4 + 1 + 2 + 2,
+        };
+        let content = match version {
+            BuildingVersion::V102 => 4 + self.content.len(),
+            _ => 0,
+        };
+        header + positions + 4 * 2 + 6 + 2 * 2 + 2 + 4 * self.parameters.len() + content
     }
 
     /// Deserializing DSP building
-    pub fn deserialize(
-        data: &[u8],
-        offset: usize,
-    ) -> Result<(Self, usize), Box<dyn std::error::Error>> {
-        let (num, offset) = read_i32(data, offset);
+    pub fn deserialize(data: &[u8], offset: usize) -> Result<(Self, usize), BlueprintError> {
+        let mut reader = BinaryReader::new(data, offset);
+        reader.set_section(Section::Buildings);
+        let building = Self::read(&mut reader, &DecodeOptions::strict(), &mut Vec::new())?;
+        Ok((building, reader.offset()))
+    }
+
+    pub(crate) fn read(
+        reader: &mut BinaryReader,
+        options: &DecodeOptions,
+        warnings: &mut Vec<DecodeWarning>,
+    ) -> Result<Self, BlueprintError> {
+        let record_start = reader.offset();
+        let num = reader.read_i32()?;
 
-        let mut offset: usize = offset;
         let index: i32;
         let item_id: i16;
         let model_index: i16;
@@ -83,93 +184,84 @@ impl BlueprintBuilding {
         let recipe_id: i16;
         let filter_id: i16;
 
-        let parameter_count: i16;
+        let parameters: Vec<i32>;
 
-        let mut parameters: Vec<i32>;
-
-        let content: String = "".to_string(); // todo implement content reading from -102
+        let mut content = String::new();
+        let mut shape = None;
+        let version = BuildingVersion::from_num(num);
 
         // wtf? why -102? -101? -100?
         if num <= -102 {
-            (index, offset) = read_i32(data, offset);
-            (item_id, offset) = read_i16(data, offset);
-            (model_index, offset) = read_i16(data, offset);
-            (area_index, offset) = read_i8(data, offset);
-            (local_offset_x, offset) = read_f32(data, offset);
-            (local_offset_y, offset) = read_f32(data, offset);
-            (local_offset_z, offset) = read_f32(data, offset);
-            (yaw, offset) = read_f32(data, offset);
-
-            let item = DysonSphereItem::try_from(item_id as i32)?;
-            if item.is_conveyor_belt() {
-                (tilt, offset) = read_f32(data, offset);
+            index = reader.read_i32()?;
+            item_id = reader.read_i16()?;
+            model_index = reader.read_i16()?;
+            area_index = reader.read_i8()?;
+            local_offset_x = reader.read_f32()?;
+            local_offset_y = reader.read_f32()?;
+            local_offset_z = reader.read_f32()?;
+            yaw = reader.read_f32()?;
+
+            let item_shape;
+            (item_shape, shape) =
+                Self::record_shape(reader, item_id, record_start + 8, options, warnings)?;
+            if item_shape == BuildingShape::Belt {
+                tilt = reader.read_f32()?;
                 pitch = 0.;
-                (local_offset_x2, offset) = read_f32(data, offset);
-                (local_offset_y2, offset) = read_f32(data, offset);
-                (local_offset_z2, offset) = read_f32(data, offset);
+                local_offset_x2 = reader.read_f32()?;
+                local_offset_y2 = reader.read_f32()?;
+                local_offset_z2 = reader.read_f32()?;
                 yaw2 = yaw;
                 tilt2 = tilt;
                 pitch2 = 0.;
-            } else if item.is_sorter() {
-                (tilt, offset) = read_f32(data, offset);
-                (pitch, offset) = read_f32(data, offset);
-                (local_offset_x2, offset) = read_f32(data, offset);
-                (local_offset_y2, offset) = read_f32(data, offset);
-                (local_offset_z2, offset) = read_f32(data, offset);
-                (yaw2, offset) = read_f32(data, offset);
-                (tilt2, offset) = read_f32(data, offset);
-                (pitch2, offset) = read_f32(data, offset);
+            } else if item_shape == BuildingShape::Sorter {
+                tilt = reader.read_f32()?;
+                pitch = reader.read_f32()?;
+                local_offset_x2 = reader.read_f32()?;
+                local_offset_y2 = reader.read_f32()?;
+                local_offset_z2 = reader.read_f32()?;
+                yaw2 = reader.read_f32()?;
+                tilt2 = reader.read_f32()?;
+                pitch2 = reader.read_f32()?;
             } else {
                 tilt = 0.;
                 pitch = 0.;
-                (local_offset_x2, offset) = read_f32(data, offset);
-                (local_offset_y2, offset) = read_f32(data, offset);
-                (local_offset_z2, offset) = read_f32(data, offset);
+                local_offset_x2 = reader.read_f32()?;
+                local_offset_y2 = reader.read_f32()?;
+                local_offset_z2 = reader.read_f32()?;
                 yaw2 = yaw;
                 tilt2 = 0.;
                 pitch2 = 0.;
             }
 
-            (output_object_index, offset) = read_i32(data, offset);
-            (input_object_index, offset) = read_i32(data, offset);
-            (output_to_slot, offset) = read_i8(data, offset);
-            (input_from_slot, offset) = read_i8(data, offset);
-            (output_from_slot, offset) = read_i8(data, offset);
-            (input_to_slot, offset) = read_i8(data, offset);
-            (output_offset, offset) = read_i8(data, offset);
-            (input_offset, offset) = read_i8(data, offset);
-            (recipe_id, offset) = read_i16(data, offset);
-            (filter_id, offset) = read_i16(data, offset);
-
-            (parameter_count, offset) = read_i16(data, offset);
+            output_object_index = reader.read_i32()?;
+            input_object_index = reader.read_i32()?;
+            output_to_slot = reader.read_i8()?;
+            input_from_slot = reader.read_i8()?;
+            output_from_slot = reader.read_i8()?;
+            input_to_slot = reader.read_i8()?;
+            output_offset = reader.read_i8()?;
+            input_offset = reader.read_i8()?;
+            recipe_id = reader.read_i16()?;
+            filter_id = reader.read_i16()?;
 
-            parameters = vec![0; parameter_count as usize];
+            parameters = Self::read_parameters(reader)?;
 
-            for param_idx in 0..parameter_count {
-                (parameters[param_idx as usize], offset) = read_i32(data, offset);
-            }
-
-            let (content_len, new_offset) = read_i32(data, offset);
-            offset = new_offset;
-
-            // todo: add content reading. now just skip
-            for _ in 0..content_len {
-                let (_, new_offset) = read_i8(data, offset);
-                offset = new_offset;
-            }
+            content = Self::read_content(reader)?;
         } else if num <= -101 {
-            (index, offset) = read_i32(data, offset);
-            (item_id, offset) = read_i16(data, offset);
-            (model_index, offset) = read_i16(data, offset);
-            (area_index, offset) = read_i8(data, offset);
-            (local_offset_x, offset) = read_f32(data, offset);
-            (local_offset_y, offset) = read_f32(data, offset);
-            (local_offset_z, offset) = read_f32(data, offset);
-            (yaw, offset) = read_f32(data, offset);
-
-            let item = DysonSphereItem::try_from(item_id as i32)?;
-            if item.is_conveyor_belt() {
-                (tilt, offset) = read_f32(data, offset);
+            index = reader.read_i32()?;
+            item_id = reader.read_i16()?;
+            model_index = reader.read_i16()?;
+            area_index = reader.read_i8()?;
+            local_offset_x = reader.read_f32()?;
+            local_offset_y = reader.read_f32()?;
+            local_offset_z = reader.read_f32()?;
+            yaw = reader.read_f32()?;
+
+            let item_shape;
+            (item_shape, shape) =
+                Self::record_shape(reader, item_id, record_start + 8, options, warnings)?;
+            if item_shape == BuildingShape::Belt {
+                tilt = reader.read_f32()?;
                 pitch = 0.;
                 local_offset_x2 = local_offset_x;
                 local_offset_y2 = local_offset_y;
@@ -177,15 +269,15 @@ impl BlueprintBuilding {
                 yaw2 = yaw;
                 tilt2 = tilt;
                 pitch2 = 0.;
-            } else if item.is_sorter() {
-                (tilt, offset) = read_f32(data, offset);
-                (pitch, offset) = read_f32(data, offset);
-                (local_offset_x2, offset) = read_f32(data, offset);
-                (local_offset_y2, offset) = read_f32(data, offset);
-                (local_offset_z2, offset) = read_f32(data, offset);
-                (yaw2, offset) = read_f32(data, offset);
-                (tilt2, offset) = read_f32(data, offset);
-                (pitch2, offset) = read_f32(data, offset);
+            } else if item_shape == BuildingShape::Sorter {
+                tilt = reader.read_f32()?;
+                pitch = reader.read_f32()?;
+                local_offset_x2 = reader.read_f32()?;
+                local_offset_y2 = reader.read_f32()?;
+                local_offset_z2 = reader.read_f32()?;
+                yaw2 = reader.read_f32()?;
+                tilt2 = reader.read_f32()?;
+                pitch2 = reader.read_f32()?;
             } else {
                 tilt = 0.;
                 pitch = 0.;
@@ -196,177 +288,1022 @@ impl BlueprintBuilding {
                 tilt2 = 0.;
                 pitch2 = 0.;
             }
-            (output_object_index, offset) = read_i32(data, offset);
-            (input_object_index, offset) = read_i32(data, offset);
-            (output_to_slot, offset) = read_i8(data, offset);
-            (input_from_slot, offset) = read_i8(data, offset);
-            (output_from_slot, offset) = read_i8(data, offset);
-            (input_to_slot, offset) = read_i8(data, offset);
-            (output_offset, offset) = read_i8(data, offset);
-            (input_offset, offset) = read_i8(data, offset);
-            (recipe_id, offset) = read_i16(data, offset);
-            (filter_id, offset) = read_i16(data, offset);
-
-            (parameter_count, offset) = read_i16(data, offset);
-
-            parameters = vec![0; parameter_count as usize];
-
-            for param_idx in 0..parameter_count {
-                (parameters[param_idx as usize], offset) = read_i32(data, offset);
-            }
+            output_object_index = reader.read_i32()?;
+            input_object_index = reader.read_i32()?;
+            output_to_slot = reader.read_i8()?;
+            input_from_slot = reader.read_i8()?;
+            output_from_slot = reader.read_i8()?;
+            input_to_slot = reader.read_i8()?;
+            output_offset = reader.read_i8()?;
+            input_offset = reader.read_i8()?;
+            recipe_id = reader.read_i16()?;
+            filter_id = reader.read_i16()?;
+
+            parameters = Self::read_parameters(reader)?;
         } else if num <= -100 {
-            (index, offset) = read_i32(data, offset);
-            (area_index, offset) = read_i8(data, offset);
-            (local_offset_x, offset) = read_f32(data, offset);
-            (local_offset_y, offset) = read_f32(data, offset);
-            (local_offset_z, offset) = read_f32(data, offset);
-            (local_offset_x2, offset) = read_f32(data, offset);
-            (local_offset_y2, offset) = read_f32(data, offset);
-            (local_offset_z2, offset) = read_f32(data, offset);
+            index = reader.read_i32()?;
+            area_index = reader.read_i8()?;
+            local_offset_x = reader.read_f32()?;
+            local_offset_y = reader.read_f32()?;
+            local_offset_z = reader.read_f32()?;
+            local_offset_x2 = reader.read_f32()?;
+            local_offset_y2 = reader.read_f32()?;
+            local_offset_z2 = reader.read_f32()?;
             pitch = 0.;
             pitch2 = 0.;
-            (yaw, offset) = read_f32(data, offset);
-            (yaw2, offset) = read_f32(data, offset);
-            (tilt, offset) = read_f32(data, offset);
+            yaw = reader.read_f32()?;
+            yaw2 = reader.read_f32()?;
+            tilt = reader.read_f32()?;
             tilt2 = 0.;
-            (item_id, offset) = read_i16(data, offset);
-            (model_index, offset) = read_i16(data, offset);
-
-            (output_object_index, offset) = read_i32(data, offset);
-            (input_object_index, offset) = read_i32(data, offset);
-            (output_to_slot, offset) = read_i8(data, offset);
-            (input_from_slot, offset) = read_i8(data, offset);
-            (output_from_slot, offset) = read_i8(data, offset);
-            (input_to_slot, offset) = read_i8(data, offset);
-            (output_offset, offset) = read_i8(data, offset);
-            (input_offset, offset) = read_i8(data, offset);
-            (recipe_id, offset) = read_i16(data, offset);
-            (filter_id, offset) = read_i16(data, offset);
-
-            (parameter_count, offset) = read_i16(data, offset);
-
-            parameters = vec![0; parameter_count as usize];
-
-            for param_idx in 0..parameter_count {
-                (parameters[param_idx as usize], offset) = read_i32(data, offset);
-            }
+            item_id = reader.read_i16()?;
+            model_index = reader.read_i16()?;
+
+            output_object_index = reader.read_i32()?;
+            input_object_index = reader.read_i32()?;
+            output_to_slot = reader.read_i8()?;
+            input_from_slot = reader.read_i8()?;
+            output_from_slot = reader.read_i8()?;
+            input_to_slot = reader.read_i8()?;
+            output_offset = reader.read_i8()?;
+            input_offset = reader.read_i8()?;
+            recipe_id = reader.read_i16()?;
+            filter_id = reader.read_i16()?;
+
+            parameters = Self::read_parameters(reader)?;
         } else {
             index = num;
-            (area_index, offset) = read_i8(data, offset);
-            (local_offset_x, offset) = read_f32(data, offset);
-            (local_offset_y, offset) = read_f32(data, offset);
-            (local_offset_z, offset) = read_f32(data, offset);
-            (local_offset_x2, offset) = read_f32(data, offset);
-            (local_offset_y2, offset) = read_f32(data, offset);
-            (local_offset_z2, offset) = read_f32(data, offset);
+            area_index = reader.read_i8()?;
+            local_offset_x = reader.read_f32()?;
+            local_offset_y = reader.read_f32()?;
+            local_offset_z = reader.read_f32()?;
+            local_offset_x2 = reader.read_f32()?;
+            local_offset_y2 = reader.read_f32()?;
+            local_offset_z2 = reader.read_f32()?;
             pitch = 0.;
             pitch2 = 0.;
-            (yaw, offset) = read_f32(data, offset);
-            (yaw2, offset) = read_f32(data, offset);
+            yaw = reader.read_f32()?;
+            yaw2 = reader.read_f32()?;
             tilt = 0.;
             tilt2 = 0.;
-            (item_id, offset) = read_i16(data, offset);
-            (model_index, offset) = read_i16(data, offset);
-
-            (output_object_index, offset) = read_i32(data, offset);
-            (input_object_index, offset) = read_i32(data, offset);
-            (output_to_slot, offset) = read_i8(data, offset);
-            (input_from_slot, offset) = read_i8(data, offset);
-            (output_from_slot, offset) = read_i8(data, offset);
-            (input_to_slot, offset) = read_i8(data, offset);
-            (output_offset, offset) = read_i8(data, offset);
-            (input_offset, offset) = read_i8(data, offset);
-            (recipe_id, offset) = read_i16(data, offset);
-            (filter_id, offset) = read_i16(data, offset);
-
-            (parameter_count, offset) = read_i16(data, offset);
-
-            parameters = vec![0; parameter_count as usize];
-
-            for param_idx in 0..parameter_count {
-                (parameters[param_idx as usize], offset) = read_i32(data, offset);
+            item_id = reader.read_i16()?;
+            model_index = reader.read_i16()?;
+
+            output_object_index = reader.read_i32()?;
+            input_object_index = reader.read_i32()?;
+            output_to_slot = reader.read_i8()?;
+            input_from_slot = reader.read_i8()?;
+            output_from_slot = reader.read_i8()?;
+            input_to_slot = reader.read_i8()?;
+            output_offset = reader.read_i8()?;
+            input_offset = reader.read_i8()?;
+            recipe_id = reader.read_i16()?;
+            filter_id = reader.read_i16()?;
+
+            parameters = Self::read_parameters(reader)?;
+        }
+        Ok(BlueprintBuilding {
+            index,
+            area_index,
+            local_offset_x,
+            local_offset_y,
+            local_offset_z,
+            local_offset_x2,
+            local_offset_y2,
+            local_offset_z2,
+            yaw,
+            yaw2,
+            pitch,
+            pitch2,
+            item_id,
+            model_index,
+            output_object_index,
+            input_object_index,
+            output_to_slot,
+            input_from_slot,
+            output_from_slot,
+            input_to_slot,
+            output_offset,
+            input_offset,
+            recipe_id,
+            filter_id,
+            tilt,
+            tilt2,
+            parameters,
+            content,
+            version,
+            shape,
+        })
+    }
+
+    /// Shape of the position block of -101 and -102 records, along with the shape assumed for
+    /// an unknown item.
+    fn record_shape(
+        reader: &BinaryReader,
+        item_id: i16,
+        item_offset: usize,
+        options: &DecodeOptions,
+        warnings: &mut Vec<DecodeWarning>,
+    ) -> Result<(BuildingShape, Option<BuildingShape>), BlueprintError> {
+        if let Ok(item) = DysonSphereItem::try_from(item_id as i32) {
+            return Ok((BuildingShape::of(item), None));
+        }
+        let location = reader.location_at(item_offset);
+        match options.unknown_item_shape(item_id) {
+            Some(shape) => {
+                warnings.push(DecodeWarning::UnknownItem {
+                    id: item_id as i32,
+                    location,
+                    shape,
+                });
+                Ok((shape, Some(shape)))
+            }
+            None => Err(BlueprintError::UnknownItem {
+                id: item_id as i32,
+                location: Some(location),
+            }),
+        }
+    }
+
+    fn read_parameters(reader: &mut BinaryReader) -> Result<Vec<i32>, BlueprintError> {
+        let parameter_count = reader.read_i16()?;
+        if parameter_count < 0 {
+            return Err(BlueprintError::CorruptedData {
+                location: reader.location_at(reader.offset() - 2),
+                reason: format!("negative parameter count {}", parameter_count),
+            });
+        }
+        reader.ensure(4 * parameter_count as usize)?;
+        let mut parameters = Vec::with_capacity(parameter_count as usize);
+        for _ in 0..parameter_count {
+            parameters.push(reader.read_i32()?);
+        }
+        Ok(parameters)
+    }
+
+    /// Reads the user text of -102 records: byte length followed by UTF-8 bytes, the way C#
+    /// `Encoding.UTF8` writes it.
+    fn read_content(reader: &mut BinaryReader) -> Result<String, BlueprintError> {
+        let content_len = reader.read_i32()?;
+        if content_len < 0 {
+            return Err(BlueprintError::CorruptedData {
+                location: reader.location_at(reader.offset() - 4),
+                reason: format!("negative content length {}", content_len),
+            });
+        }
+        let start = reader.offset();
+        let bytes = reader.read_bytes(content_len as usize)?;
+        String::from_utf8(bytes.to_vec()).map_err(|e| BlueprintError::CorruptedData {
+            location: reader.location_at(start + e.utf8_error().valid_up_to()),
+            reason: "content is not valid UTF-8".to_string(),
+        })
+    }
+
+    /// Appends the building record to `data` using the layout of [`BlueprintBuilding::version`].
+    pub fn serialize(&self, data: &mut Vec<u8>) -> Result<(), BlueprintError> {
+        self.serialize_as(data, self.version)
+    }
+
+    /// Appends the building record to `data` using the `version` layout.
+    ///
+    /// Fails with [`BlueprintError::LossyBuildingVersion`] if the layout can't hold one of the fields, e.g. the
+    /// sorter pitch or `content` when writing a -100 record.
+    pub fn serialize_as(
+        &self,
+        data: &mut Vec<u8>,
+        version: BuildingVersion,
+    ) -> Result<(), BlueprintError> {
+        if self.parameters.len() > i16::MAX as usize {
+            return Err(BlueprintError::InvalidCount {
+                what: "building parameters",
+                count: self.parameters.len(),
+            });
+        }
+        let shape = self.shape();
+        if version.depends_on_item() && self.shape.is_none() {
+            // newer layouts depend on the kind of building, so the item has to be known
+            DysonSphereItem::try_from(self.item_id as i32)?;
+        }
+        if let Some(field) = self.lost_field(version) {
+            return Err(BlueprintError::LossyBuildingVersion {
+                building_index: self.index,
+                version: version.num(),
+                field,
+            });
+        }
+
+        match version {
+            BuildingVersion::Legacy | BuildingVersion::V100 => {
+                if version == BuildingVersion::Legacy {
+                    write_i32(data, self.index);
+                } else {
+                    write_i32(data, version.num());
+                    write_i32(data, self.index);
+                }
+                write_i8(data, self.area_index);
+                write_f32(data, self.local_offset_x);
+                write_f32(data, self.local_offset_y);
+                write_f32(data, self.local_offset_z);
+                write_f32(data, self.local_offset_x2);
+                write_f32(data, self.local_offset_y2);
+                write_f32(data, self.local_offset_z2);
+                write_f32(data, self.yaw);
+                write_f32(data, self.yaw2);
+                if version == BuildingVersion::V100 {
+                    write_f32(data, self.tilt);
+                }
+                write_i16(data, self.item_id);
+                write_i16(data, self.model_index);
             }
+            BuildingVersion::V101 | BuildingVersion::V102 => {
+                write_i32(data, version.num());
+                write_i32(data, self.index);
+                write_i16(data, self.item_id);
+                write_i16(data, self.model_index);
+                write_i8(data, self.area_index);
+                write_f32(data, self.local_offset_x);
+                write_f32(data, self.local_offset_y);
+                write_f32(data, self.local_offset_z);
+                write_f32(data, self.yaw);
+                match shape {
+                    BuildingShape::Belt => {
+                        write_f32(data, self.tilt);
+                        if version == BuildingVersion::V102 {
+                            write_f32(data, self.local_offset_x2);
+                            write_f32(data, self.local_offset_y2);
+                            write_f32(data, self.local_offset_z2);
+                        }
+                    }
+                    BuildingShape::Sorter => {
+                        write_f32(data, self.tilt);
+                        write_f32(data, self.pitch);
+                        write_f32(data, self.local_offset_x2);
+                        write_f32(data, self.local_offset_y2);
+                        write_f32(data, self.local_offset_z2);
+                        write_f32(data, self.yaw2);
+                        write_f32(data, self.tilt2);
+                        write_f32(data, self.pitch2);
+                    }
+                    BuildingShape::Other => {
+                        if version == BuildingVersion::V102 {
+                            write_f32(data, self.local_offset_x2);
+                            write_f32(data, self.local_offset_y2);
+                            write_f32(data, self.local_offset_z2);
+                        }
+                    }
+                }
+            }
+        }
+
+        write_i32(data, self.output_object_index);
+        write_i32(data, self.input_object_index);
+        write_i8(data, self.output_to_slot);
+        write_i8(data, self.input_from_slot);
+        write_i8(data, self.output_from_slot);
+        write_i8(data, self.input_to_slot);
+        write_i8(data, self.output_offset);
+        write_i8(data, self.input_offset);
+        write_i16(data, self.recipe_id);
+        write_i16(data, self.filter_id);
+
+        write_i16(data, self.parameters.len() as i16);
+        for parameter in self.parameters.iter() {
+            write_i32(data, *parameter);
+        }
+
+        if version == BuildingVersion::V102 {
+            write_i32(data, self.content.len() as i32);
+            data.extend_from_slice(self.content.as_bytes());
         }
-        Ok((
-            BlueprintBuilding {
-                index,
-                area_index,
-                local_offset_x,
-                local_offset_y,
-                local_offset_z,
-                local_offset_x2,
-                local_offset_y2,
-                local_offset_z2,
-                yaw,
-                yaw2,
-                pitch,
-                pitch2,
-                item_id,
-                model_index,
-                output_object_index,
-                input_object_index,
-                output_to_slot,
-                input_from_slot,
-                output_from_slot,
-                input_to_slot,
-                output_offset,
-                input_offset,
-                recipe_id,
-                filter_id,
-                tilt,
-                tilt2,
-                parameters,
-                content,
+        Ok(())
+    }
+
+    /// Shape of the position block in -101 and -102 records.
+    ///
+    /// Known items decide it themselves, unknown ones use the `shape` field.
+    pub fn shape(&self) -> BuildingShape {
+        match self.item() {
+            Some(item) => BuildingShape::of(item),
+            None => self.shape.unwrap_or(BuildingShape::Other),
+        }
+    }
+
+    /// The oldest version that holds the building without losing anything.
+    pub fn oldest_lossless_version(&self) -> Option<BuildingVersion> {
+        [
+            BuildingVersion::Legacy,
+            BuildingVersion::V100,
+            BuildingVersion::V101,
+            BuildingVersion::V102,
+        ]
+        .into_iter()
+        .find(|version| {
+            self.lost_field(*version).is_none()
+                && (self.item().is_some() || self.shape.is_some() || !version.depends_on_item())
+        })
+    }
+
+    /// Returns the first field which decoding the building written with `version` would not
+    /// give back.
+    fn lost_field(&self, version: BuildingVersion) -> Option<&'static str> {
+        let shape = self.shape();
+        let checks: &[(&'static str, bool)] = match version {
+            BuildingVersion::Legacy => &[
+                ("index", self.index > -100),
+                ("tilt", self.tilt == 0.),
+                ("tilt2", self.tilt2 == 0.),
+                ("pitch", self.pitch == 0.),
+                ("pitch2", self.pitch2 == 0.),
+                ("content", self.content.is_empty()),
+            ],
+            BuildingVersion::V100 => &[
+                ("tilt2", self.tilt2 == 0.),
+                ("pitch", self.pitch == 0.),
+                ("pitch2", self.pitch2 == 0.),
+                ("content", self.content.is_empty()),
+            ],
+            BuildingVersion::V101 | BuildingVersion::V102 => match shape {
+                BuildingShape::Sorter => &[],
+                BuildingShape::Belt => &[
+                    ("yaw2", self.yaw2 == self.yaw),
+                    ("tilt2", self.tilt2 == self.tilt),
+                    ("pitch", self.pitch == 0.),
+                    ("pitch2", self.pitch2 == 0.),
+                ],
+                BuildingShape::Other => &[
+                    ("yaw2", self.yaw2 == self.yaw),
+                    ("tilt", self.tilt == 0.),
+                    ("tilt2", self.tilt2 == 0.),
+                    ("pitch", self.pitch == 0.),
+                    ("pitch2", self.pitch2 == 0.),
+                ],
             },
-            offset,
-        ))
+        };
+        if let Some((field, _)) = checks.iter().find(|(_, fits)| !fits) {
+            return Some(field);
+        }
+        if version == BuildingVersion::V101 {
+            if !self.content.is_empty() {
+                return Some("content");
+            }
+            let same_end = self.local_offset_x2 == self.local_offset_x
+                && self.local_offset_y2 == self.local_offset_y
+                && self.local_offset_z2 == self.local_offset_z;
+            if shape != BuildingShape::Sorter && !same_end {
+                return Some("local_offset2");
+            }
+        }
+        None
     }
 
     /// If item_id is unknown id – returns None.
     pub fn item(&self) -> Option<DysonSphereItem> {
-        let item = (self.item_id as i32).try_into();
-        match item {
-            Err(_) => None,
-            Ok(item) => Some(item),
-        }
+        DysonSphereItem::try_from(self.item_id as i32).ok()
     }
 
     /// Returns parsed BlueprintBuildingParameters
+    ///
+    /// Parameters which don't fit the layout of the building are returned as `Raw`.
     pub fn get_parameters(&self) -> BlueprintBuildingParameters {
-        match self.item() {
-            Some(DysonSphereItem::PlanetaryLogisticsStation) => {
-                BlueprintBuildingParameters::Station(StationParameters::new(
-                    &self.parameters,
-                    4,
-                    12,
-                ))
+        let raw = || BlueprintBuildingParameters::Raw(self.parameters.clone());
+        let Some(item) = self.item() else {
+            return raw();
+        };
+        if let Some(kind) = StationKind::of(item) {
+            return match StationParameters::new(
+                &self.parameters,
+                kind.storage_len(),
+                kind.slots_len(),
+            ) {
+                Ok(station) => BlueprintBuildingParameters::Station(station),
+                Err(_) => raw(),
+            };
+        }
+        if RecipeClass::of(item).is_some() {
+            return BlueprintBuildingParameters::Assembler(AssemblerParameters::new(
+                &self.parameters,
+                self.recipe_id,
+            ));
+        }
+        if GeneratorParameters::is_generator(item) {
+            return BlueprintBuildingParameters::Generator(GeneratorParameters::new(
+                &self.parameters,
+            ));
+        }
+        if LabParameters::is_lab(item) {
+            return BlueprintBuildingParameters::Lab(LabParameters::new(
+                &self.parameters,
+                self.recipe_id,
+            ));
+        }
+        if item.is_sorter() {
+            return BlueprintBuildingParameters::Sorter(SorterParameters::new(
+                &self.parameters,
+                self.filter_id,
+            ));
+        }
+        if item.is_conveyor_belt() {
+            return BlueprintBuildingParameters::Belt(BeltParameters::new(&self.parameters));
+        }
+        if let Some(slot_count) = StorageParameters::slot_count(item) {
+            return BlueprintBuildingParameters::Storage(StorageParameters::new(
+                &self.parameters,
+                slot_count,
+            ));
+        }
+        match item {
+            DysonSphereItem::StorageTank => {
+                BlueprintBuildingParameters::Tank(TankParameters::new(&self.parameters))
             }
-            Some(DysonSphereItem::InterstellarLogisticsStation) => {
-                BlueprintBuildingParameters::Station(StationParameters::new(
-                    &self.parameters,
-                    5,
-                    12,
-                ))
+            DysonSphereItem::RayReceiver => BlueprintBuildingParameters::RayReceiver(
+                RayReceiverParameters::new(&self.parameters),
+            ),
+            DysonSphereItem::EnergyExchanger => {
+                BlueprintBuildingParameters::Exchanger(ExchangerParameters::new(&self.parameters))
             }
-            _ => BlueprintBuildingParameters::Raw(self.parameters.clone()),
+            DysonSphereItem::EMRailEjector => {
+                BlueprintBuildingParameters::Ejector(EjectorParameters::new(&self.parameters))
+            }
+            DysonSphereItem::Splitter => BlueprintBuildingParameters::Splitter(
+                SplitterParameters::new(&self.parameters, self.filter_id),
+            ),
+            _ => raw(),
         }
     }
+
+    /// Writes typed `parameters` back into the parameter array, `filter_id` and `recipe_id`.
+    ///
+    /// Ints the typed parameters don't model keep their values, so a station can be changed
+    /// through [`BlueprintBuilding::get_parameters`] without losing what isn't decoded.
+    pub fn set_parameters(
+        &mut self,
+        parameters: &BlueprintBuildingParameters,
+    ) -> Result<(), BlueprintError> {
+        parameters.write(&mut self.parameters)?;
+        if let Some(filter_id) = parameters.filter_id() {
+            self.filter_id = filter_id;
+        }
+        if let Some(recipe_id) = parameters.recipe_id() {
+            self.recipe_id = recipe_id;
+        }
+        Ok(())
+    }
+
+    /// Changes the station parameters with `update`, returns `false` if this isn't a station.
+    pub fn update_station<F>(&mut self, update: F) -> Result<bool, BlueprintError>
+    where
+        F: FnOnce(&mut StationParameters),
+    {
+        let BlueprintBuildingParameters::Station(mut station) = self.get_parameters() else {
+            return Ok(false);
+        };
+        update(&mut station);
+        station.write(&mut self.parameters)?;
+        Ok(true)
+    }
+
+    /// Turns a logistics station into a station of `kind`, see [`StationParameters::convert`].
+    ///
+    /// Returns `false` if this isn't a logistics station or it's already of `kind`.
+    pub fn convert_station(&mut self, kind: StationKind) -> Result<bool, BlueprintError> {
+        let BlueprintBuildingParameters::Station(mut station) = self.get_parameters() else {
+            return Ok(false);
+        };
+        let Some(model_index) = kind.model_index() else {
+            return Ok(false);
+        };
+        if !station.convert(kind) {
+            return Ok(false);
+        }
+        // the storage a planetary station doesn't have isn't written, so clear it
+        let unused = kind.storage_len() * 6..30;
+        if let Some(unused) = self.parameters.get_mut(unused) {
+            unused.fill(0);
+        }
+        station.write(&mut self.parameters)?;
+        self.item_id = kind.item() as i16;
+        self.model_index = model_index;
+        Ok(true)
+    }
+}
+
+/// Binary layouts of a building record, selected by the leading number of the record.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Default)]
+pub enum BuildingVersion {
+    /// The leading number is the building index itself.
+    Legacy,
+    /// -100: both ends and yaws, tilt of the first end.
+    V100,
+    /// -101: item and model go first, the rest depends on whether it's a belt or a sorter.
+    V101,
+    /// -102: like -101, but the second end is stored for every building and `content` follows.
+    #[default]
+    V102,
+}
+
+impl BuildingVersion {
+    /// Version of the record starting with `num`.
+    pub fn from_num(num: i32) -> Self {
+        if num <= -102 {
+            BuildingVersion::V102
+        } else if num <= -101 {
+            BuildingVersion::V101
+        } else if num <= -100 {
+            BuildingVersion::V100
+        } else {
+            BuildingVersion::Legacy
+        }
+    }
+
+    /// Leading number of the record, `0` for legacy records which start with the index.
+    pub fn num(&self) -> i32 {
+        match self {
+            BuildingVersion::Legacy => 0,
+            BuildingVersion::V100 => -100,
+            BuildingVersion::V101 => -101,
+            BuildingVersion::V102 => -102,
+        }
+    }
+
+    fn depends_on_item(&self) -> bool {
+        matches!(self, BuildingVersion::V101 | BuildingVersion::V102)
+    }
+}
+
+/// Kinds of buildings which store their positions differently in -101 and -102 records.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum BuildingShape {
+    Belt,
+    Sorter,
+    Other,
 }
 
-#[derive(Debug, Serialize, PartialEq)]
+impl BuildingShape {
+    pub fn of(item: DysonSphereItem) -> Self {
+        if item.is_conveyor_belt() {
+            BuildingShape::Belt
+        } else if item.is_sorter() {
+            BuildingShape::Sorter
+        } else {
+            BuildingShape::Other
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub enum BlueprintBuildingParameters {
     Station(StationParameters),
-    // todo: add other buildings
-    // Splitter(SplitterParameters),
+    Splitter(SplitterParameters),
+    Assembler(AssemblerParameters),
+    Storage(StorageParameters),
+    Tank(TankParameters),
+    Belt(BeltParameters),
+    Sorter(SorterParameters),
+    Lab(LabParameters),
+    RayReceiver(RayReceiverParameters),
+    Exchanger(ExchangerParameters),
+    Generator(GeneratorParameters),
+    Ejector(EjectorParameters),
+    /// Parameters of buildings without a typed layout, kept as they are.
     Raw(Vec<i32>),
 }
 
 impl BlueprintBuildingParameters {
+    /// Writes the parameters into `params`.
+    ///
+    /// Typed parameters leave the ints they don't model untouched, raw ones replace the array.
+    pub fn write(&self, params: &mut Vec<i32>) -> Result<(), BlueprintError> {
+        match self {
+            BlueprintBuildingParameters::Station(station) => station.write(params)?,
+            BlueprintBuildingParameters::Splitter(splitter) => splitter.write(params),
+            BlueprintBuildingParameters::Assembler(assembler) => assembler.write(params),
+            BlueprintBuildingParameters::Storage(storage) => storage.write(params),
+            BlueprintBuildingParameters::Tank(tank) => tank.write(params),
+            BlueprintBuildingParameters::Belt(belt) => belt.write(params),
+            BlueprintBuildingParameters::Sorter(sorter) => sorter.write(params),
+            BlueprintBuildingParameters::Lab(lab) => lab.write(params),
+            BlueprintBuildingParameters::RayReceiver(receiver) => receiver.write(params),
+            BlueprintBuildingParameters::Exchanger(exchanger) => exchanger.write(params),
+            BlueprintBuildingParameters::Generator(generator) => generator.write(params),
+            BlueprintBuildingParameters::Ejector(ejector) => ejector.write(params),
+            BlueprintBuildingParameters::Raw(raw) => params.clone_from(raw),
+        }
+        Ok(())
+    }
+
+    /// `filter_id` of the building the parameters are kept in, if they carry it.
+    pub fn filter_id(&self) -> Option<i16> {
+        match self {
+            BlueprintBuildingParameters::Splitter(splitter) => Some(splitter.filter_id),
+            BlueprintBuildingParameters::Sorter(sorter) => Some(sorter.filter_id),
+            _ => None,
+        }
+    }
+
+    /// `recipe_id` of the building the parameters are kept in, if they carry it.
+    pub fn recipe_id(&self) -> Option<i16> {
+        match self {
+            BlueprintBuildingParameters::Assembler(assembler) => Some(assembler.recipe_id),
+            BlueprintBuildingParameters::Lab(lab) => Some(lab.recipe_id),
+            _ => None,
+        }
+    }
+
     pub fn to_json(&self) -> String {
         serde_json::to_string_pretty(self).unwrap()
     }
 }
 
+#[cfg(test)]
+mod serialize_tests {
+    use super::*;
+
+    fn round_trip(building: &BlueprintBuilding) -> i32 {
+        let mut building = building.clone();
+        building.version = building.oldest_lossless_version().unwrap();
+        let mut data = Vec::new();
+        building.serialize(&mut data).unwrap();
+        assert_eq!(data.len(), building.size());
+        let (decoded, offset) = BlueprintBuilding::deserialize(&data, 0).unwrap();
+        assert_eq!(offset, data.len());
+        assert_eq!(decoded, building);
+        BinaryReader::new(&data, 0).read_i32().unwrap()
+    }
+
+    #[test]
+    fn test_plain_building_uses_legacy_layout() {
+        let mut building = BlueprintBuilding::new(3, DysonSphereItem::AssemblingMachineMkI);
+        building.local_offset_x = 1.5;
+        building.local_offset_y = -2.0;
+        building.local_offset_x2 = 1.5;
+        building.local_offset_y2 = -2.0;
+        building.yaw = 90.0;
+        building.yaw2 = 90.0;
+        building.parameters = vec![1, 2, 3];
+        assert_eq!(round_trip(&building), building.index);
+    }
+
+    #[test]
+    fn test_tilted_belt_layouts() {
+        let mut belt = BlueprintBuilding::new(7, DysonSphereItem::ConveyorBeltMKIII);
+        belt.tilt = 12.5;
+        belt.tilt2 = 12.5;
+        assert_eq!(round_trip(&belt), -101);
+
+        belt.local_offset_z2 = 1.25;
+        assert_eq!(round_trip(&belt), -102);
+    }
+
+    #[test]
+    fn test_sorter_layout() {
+        let mut sorter = BlueprintBuilding::new(8, DysonSphereItem::SorterMKII);
+        sorter.pitch = 5.0;
+        sorter.pitch2 = 7.0;
+        sorter.yaw2 = 180.0;
+        sorter.local_offset_x2 = 3.5;
+        sorter.parameters = vec![1];
+        assert_eq!(round_trip(&sorter), -101);
+    }
+
+    #[test]
+    fn test_keeps_source_version() {
+        for version in [
+            BuildingVersion::Legacy,
+            BuildingVersion::V100,
+            BuildingVersion::V101,
+            BuildingVersion::V102,
+        ] {
+            let mut building = BlueprintBuilding::new(1, DysonSphereItem::Smelter);
+            building.recipe_id = 1;
+            building.version = version;
+            let mut data = Vec::new();
+            building.serialize(&mut data).unwrap();
+            let (decoded, _) = BlueprintBuilding::deserialize(&data, 0).unwrap();
+            assert_eq!(decoded.version, version);
+            assert_eq!(decoded, building);
+        }
+    }
+
+    #[test]
+    fn test_content() {
+        let mut marker = BlueprintBuilding::new(2, DysonSphereItem::Smelter);
+        marker.content = "Iron → Gears ⚙".to_string();
+        let mut data = Vec::new();
+        marker.serialize(&mut data).unwrap();
+
+        let content_bytes = marker.content.as_bytes();
+        let content_len_offset = data.len() - content_bytes.len() - 4;
+        assert_eq!(
+            BinaryReader::new(&data, content_len_offset)
+                .read_i32()
+                .unwrap(),
+            content_bytes.len() as i32
+        );
+        assert_eq!(&data[content_len_offset + 4..], content_bytes);
+
+        let (decoded, offset) = BlueprintBuilding::deserialize(&data, 0).unwrap();
+        assert_eq!(offset, data.len());
+        assert_eq!(decoded.content, marker.content);
+        assert!(serde_json::to_string(&decoded)
+            .unwrap()
+            .contains("\"content\":\"Iron → Gears ⚙\""));
+    }
+
+    #[test]
+    fn test_invalid_content() {
+        let mut data = Vec::new();
+        BlueprintBuilding::new(2, DysonSphereItem::Smelter)
+            .serialize(&mut data)
+            .unwrap();
+        let content_len_offset = data.len() - 4;
+
+        data.truncate(content_len_offset);
+        data.extend_from_slice(&(-1i32).to_le_bytes());
+        assert!(BlueprintBuilding::deserialize(&data, 0).is_err());
+
+        data.truncate(content_len_offset);
+        data.extend_from_slice(&2i32.to_le_bytes());
+        data.extend_from_slice(&[0xff, 0xfe]);
+        assert!(BlueprintBuilding::deserialize(&data, 0).is_err());
+
+        data.truncate(content_len_offset);
+        data.extend_from_slice(&8i32.to_le_bytes());
+        assert!(BlueprintBuilding::deserialize(&data, 0).is_err());
+    }
+
+    #[test]
+    fn test_lossy_downgrade() {
+        let mut sorter = BlueprintBuilding::new(8, DysonSphereItem::SorterMKI);
+        sorter.pitch = 5.0;
+        let err = sorter
+            .serialize_as(&mut Vec::new(), BuildingVersion::V100)
+            .unwrap_err();
+        assert!(err.to_string().contains("pitch"), "{}", err);
+
+        let mut marker = BlueprintBuilding::new(2, DysonSphereItem::Smelter);
+        marker.content = "hello".to_string();
+        assert!(marker
+            .serialize_as(&mut Vec::new(), BuildingVersion::V101)
+            .is_err());
+        assert!(marker
+            .serialize_as(&mut Vec::new(), BuildingVersion::V102)
+            .is_ok());
+    }
+}
+
+#[cfg(test)]
+mod parameters_tests {
+    use super::*;
+
+    #[test]
+    fn test_update_station() {
+        let mut station = BlueprintBuilding::new(1, DysonSphereItem::InterstellarLogisticsStation);
+        station.parameters = vec![0; 2048];
+        station.parameters[0..4].copy_from_slice(&[DysonSphereItem::IronOre as i32, 1, 2, 100]);
+        station.parameters[2000] = 7;
+
+        assert!(station
+            .update_station(|params| params.parameters.drone_count = 50)
+            .unwrap());
+        assert_eq!(station.parameters[320 + 6], 50);
+        assert_eq!(
+            station.parameters[0..4],
+            [DysonSphereItem::IronOre as i32, 1, 2, 100]
+        );
+        assert_eq!(station.parameters[2000], 7);
+
+        // too short for the station layout, so it isn't decoded
+        station.parameters.truncate(100);
+        assert!(!station.update_station(|_| unreachable!()).unwrap());
+
+        let mut belt = BlueprintBuilding::new(2, DysonSphereItem::ConveyorBeltMKI);
+        assert!(!belt.update_station(|_| unreachable!()).unwrap());
+    }
+
+    #[test]
+    fn test_convert_station() {
+        let mut station = BlueprintBuilding::new(1, DysonSphereItem::InterstellarLogisticsStation);
+        station.model_index = 50;
+        station.parameters = vec![0; 2048];
+        station.parameters[24..28].copy_from_slice(&[
+            DysonSphereItem::SpaceWarper as i32,
+            0,
+            2,
+            100,
+        ]);
+
+        assert!(station.convert_station(StationKind::Planetary).unwrap());
+        assert_eq!(
+            station.item(),
+            Some(DysonSphereItem::PlanetaryLogisticsStation)
+        );
+        assert_eq!(station.model_index, 49);
+        assert_eq!(station.parameters[24..30], [0; 6]);
+        assert_eq!(station.parameters.len(), 2048);
+        assert!(!station.convert_station(StationKind::Planetary).unwrap());
+        // only logistics stations are converted
+        assert!(!station
+            .convert_station(StationKind::OrbitalCollector)
+            .unwrap());
+
+        let mut belt = BlueprintBuilding::new(2, DysonSphereItem::ConveyorBeltMKI);
+        assert!(!belt.convert_station(StationKind::Interstellar).unwrap());
+    }
+
+    #[test]
+    fn test_station_like_buildings() {
+        let mut miner = BlueprintBuilding::new(1, DysonSphereItem::AdvancedMiningMachine);
+        miner.parameters = vec![0; 2048];
+        miner.parameters[320 + 9] = 300;
+        let BlueprintBuildingParameters::Station(params) = miner.get_parameters() else {
+            panic!("advanced mining machine isn't decoded");
+        };
+        assert_eq!(params.kind(), Some(StationKind::AdvancedMiningMachine));
+
+        let original = miner.parameters.clone();
+        assert!(miner
+            .update_station(|params| params.parameters.mining_speed = 200)
+            .unwrap());
+        assert_eq!(miner.parameters[320 + 9], 200);
+        miner.parameters[320 + 9] = 300;
+        assert_eq!(miner.parameters, original);
+        assert!(!miner.convert_station(StationKind::Planetary).unwrap());
+
+        let mut collector = BlueprintBuilding::new(2, DysonSphereItem::OrbitalCollector);
+        collector.parameters = vec![0; 2048];
+        let BlueprintBuildingParameters::Station(params) = collector.get_parameters() else {
+            panic!("orbital collector isn't decoded");
+        };
+        assert_eq!(params.kind(), Some(StationKind::OrbitalCollector));
+    }
+
+    #[test]
+    fn test_splitter() {
+        let mut splitter = BlueprintBuilding::new(1, DysonSphereItem::Splitter);
+        splitter.filter_id = DysonSphereItem::IronIngot as i16;
+        let BlueprintBuildingParameters::Splitter(mut params) = splitter.get_parameters() else {
+            panic!("splitter isn't decoded");
+        };
+        assert_eq!(params.filter(), Some(DysonSphereItem::IronIngot));
+
+        params.sides[2].output_priority = true;
+        params.filter_id = DysonSphereItem::CopperIngot as i16;
+        splitter
+            .set_parameters(&BlueprintBuildingParameters::Splitter(params))
+            .unwrap();
+        assert_eq!(splitter.parameters, [0, 0, 2]);
+        assert_eq!(splitter.filter_id, DysonSphereItem::CopperIngot as i16);
+    }
+
+    #[test]
+    fn test_assembler() {
+        use crate::entities::recipe::Recipe;
+        use crate::parsers::factory_bp::assembler::ProliferatorMode;
+
+        let mut assembler = BlueprintBuilding::new(1, DysonSphereItem::AssemblingMachineMkII);
+        assembler.parameters = vec![0];
+        assembler.recipe_id = Recipe::Glass as i16;
+        let BlueprintBuildingParameters::Assembler(mut params) = assembler.get_parameters() else {
+            panic!("assembler isn't decoded");
+        };
+        assert_eq!(params.recipe(), Some(Recipe::Glass));
+        assert!(!params.fits(DysonSphereItem::AssemblingMachineMkII));
+
+        params.recipe_id = Recipe::Processor as i16;
+        params.proliferator_mode = ProliferatorMode::ProductionSpeedup;
+        assembler
+            .set_parameters(&BlueprintBuildingParameters::Assembler(params))
+            .unwrap();
+        assert_eq!(assembler.recipe_id, Recipe::Processor as i16);
+        assert_eq!(assembler.parameters, [1]);
+
+        let smelter = BlueprintBuilding::new(2, DysonSphereItem::Smelter);
+        assert!(matches!(
+            smelter.get_parameters(),
+            BlueprintBuildingParameters::Assembler(_)
+        ));
+    }
+
+    #[test]
+    fn test_storage() {
+        let mut storage = BlueprintBuilding::new(1, DysonSphereItem::StorageMKII);
+        storage.parameters = vec![0; 61];
+        let BlueprintBuildingParameters::Storage(mut params) = storage.get_parameters() else {
+            panic!("storage isn't decoded");
+        };
+        assert_eq!(params.filters.len(), 60);
+        assert_eq!(params.usable_slots(), 60);
+        params.banned_slots = 20;
+        params.filters[0] = DysonSphereItem::Gear as i32;
+        storage
+            .set_parameters(&BlueprintBuildingParameters::Storage(params))
+            .unwrap();
+        assert_eq!(storage.parameters[..2], [20, DysonSphereItem::Gear as i32]);
+        assert_eq!(storage.parameters.len(), 61);
+
+        let mut tank = BlueprintBuilding::new(2, DysonSphereItem::StorageTank);
+        tank.parameters = vec![1, 1];
+        assert_eq!(
+            tank.get_parameters(),
+            BlueprintBuildingParameters::Tank(Default::default())
+        );
+    }
+
+    #[test]
+    fn test_belt() {
+        use crate::entities::signal::Signal;
+
+        let mut belt = BlueprintBuilding::new(1, DysonSphereItem::ConveyorBeltMKI);
+        belt.filter_id = 5;
+        assert_eq!(
+            belt.get_parameters(),
+            BlueprintBuildingParameters::Belt(BeltParameters::default())
+        );
+        // belts carry no filter, so it's left alone
+        belt.set_parameters(&BlueprintBuildingParameters::Belt(BeltParameters {
+            icon: Signal::from(DysonSphereItem::IronOre),
+            number: 2,
+        }))
+        .unwrap();
+        assert_eq!(belt.filter_id, 5);
+        assert_eq!(belt.parameters, [DysonSphereItem::IronOre as i32, 2]);
+    }
+
+    #[test]
+    fn test_sorter() {
+        let mut sorter = BlueprintBuilding::new(1, DysonSphereItem::SorterMKIII);
+        sorter.parameters = vec![3];
+        let BlueprintBuildingParameters::Sorter(mut params) = sorter.get_parameters() else {
+            panic!("sorter isn't decoded");
+        };
+        assert_eq!(params.length, 3);
+
+        params.length = 2;
+        sorter
+            .set_parameters(&BlueprintBuildingParameters::Sorter(params))
+            .unwrap();
+        assert_eq!(sorter.parameters, [2]);
+    }
+
+    #[test]
+    fn test_power() {
+        use crate::parsers::factory_bp::power::{ExchangerMode, RayReceiverMode};
+
+        let mut receiver = BlueprintBuilding::new(1, DysonSphereItem::RayReceiver);
+        let BlueprintBuildingParameters::RayReceiver(mut params) = receiver.get_parameters() else {
+            panic!("ray receiver isn't decoded");
+        };
+        assert_eq!(params.mode, RayReceiverMode::Power);
+        params.mode = RayReceiverMode::CriticalPhotons;
+        params.use_lens = true;
+        receiver
+            .set_parameters(&BlueprintBuildingParameters::RayReceiver(params))
+            .unwrap();
+        assert_eq!(receiver.parameters, [1208, 1]);
+
+        let mut exchanger = BlueprintBuilding::new(2, DysonSphereItem::EnergyExchanger);
+        exchanger.parameters = vec![-1];
+        let BlueprintBuildingParameters::Exchanger(params) = exchanger.get_parameters() else {
+            panic!("exchanger isn't decoded");
+        };
+        assert_eq!(params.mode, ExchangerMode::Discharge);
+
+        for item in [
+            DysonSphereItem::ThermalPowerStation,
+            DysonSphereItem::MiniFusionPowerStation,
+            DysonSphereItem::ArtificialStar,
+        ] {
+            let mut generator = BlueprintBuilding::new(3, item);
+            generator.parameters = vec![1803];
+            assert_eq!(
+                generator.get_parameters(),
+                BlueprintBuildingParameters::Generator(GeneratorParameters { fuel_id: 1803 })
+            );
+        }
+    }
+
+    #[test]
+    fn test_ejector() {
+        let mut ejector = BlueprintBuilding::new(1, DysonSphereItem::EMRailEjector);
+        assert_eq!(
+            ejector.get_parameters(),
+            BlueprintBuildingParameters::Ejector(EjectorParameters { orbit_id: 0 })
+        );
+        ejector
+            .set_parameters(&BlueprintBuildingParameters::Ejector(EjectorParameters {
+                orbit_id: 2,
+            }))
+            .unwrap();
+        assert_eq!(ejector.parameters, [2]);
+
+        let mut silo = BlueprintBuilding::new(2, DysonSphereItem::VerticalLaunchingSilo);
+        silo.parameters = vec![1];
+        assert_eq!(
+            silo.get_parameters(),
+            BlueprintBuildingParameters::Raw(vec![1])
+        );
+    }
+}
+
 #[cfg(FALSE)]
 #[cfg(test)]
 mod tests {
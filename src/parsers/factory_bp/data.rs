@@ -5,11 +5,23 @@
 
 #![allow(unexpected_cfgs)]
 
-use crate::errors::{CorruptedData, InvalidDataCount};
+use crate::entities::item::DysonSphereItem;
+use crate::errors::{BlueprintError, Section};
 use crate::parsers::factory_bp::area::BlueprintArea;
-use crate::parsers::factory_bp::building::BlueprintBuilding;
-use crate::parsers::factory_bp::utils::{read_i32, read_i8};
+use crate::parsers::factory_bp::belt::BeltParameters;
+use crate::parsers::factory_bp::building::{
+    BlueprintBuilding, BlueprintBuildingParameters, BuildingVersion,
+};
+use crate::parsers::factory_bp::decode::{
+    DecodeFailure, DecodeOptions, DecodeWarning, PartialDecode,
+};
+use crate::parsers::factory_bp::ejector::EjectorParameters;
+use crate::parsers::factory_bp::lab::{LabMode, LabParameters};
+use crate::parsers::factory_bp::sorter::{SorterConnection, SorterEnd};
+use crate::parsers::factory_bp::station::{StationKind, StationParameters};
+use crate::parsers::factory_bp::utils::{write_i32, write_i8, BinaryReader};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct BlueprintDataHeader {
@@ -26,59 +38,82 @@ pub struct BlueprintDataHeader {
 impl BlueprintDataHeader {
     pub const SIZE: usize = 4 * 7 + 1; // 29 bytes
 
-    pub fn deserialize(
-        data: &[u8],
-        offset: usize,
-    ) -> Result<(Self, usize), Box<dyn std::error::Error>> {
-        if data.len() < BlueprintDataHeader::SIZE + offset {
-            return Err(InvalidDataCount(data.len()).into());
-        }
-        let (patch, offset) = read_i32(data, offset);
-        let (cursor_offset_x, offset) = read_i32(data, offset);
-        let (cursor_offset_y, offset) = read_i32(data, offset);
-        let (cursor_target_area, offset) = read_i32(data, offset);
-        let (dragbox_size_x, offset) = read_i32(data, offset);
-        let (dragbox_size_y, offset) = read_i32(data, offset);
-        let (primary_area_index, offset) = read_i32(data, offset);
-        let (area_count, offset) = read_i8(data, offset);
-        if !(0..=64).contains(&area_count)
-            || primary_area_index < -1
-            || primary_area_index > area_count as i32
-        {
-            return Err(CorruptedData.into());
-        }
-        Ok((
-            BlueprintDataHeader {
-                patch,
-                cursor_offset_x,
-                cursor_offset_y,
-                cursor_target_area,
-                dragbox_size_x,
-                dragbox_size_y,
-                primary_area_index,
-                area_count,
-            },
-            offset,
-        ))
+    pub fn deserialize(data: &[u8], offset: usize) -> Result<(Self, usize), BlueprintError> {
+        let mut reader = BinaryReader::new(data, offset);
+        let header = Self::read(&mut reader)?;
+        Ok((header, reader.offset()))
+    }
+
+    pub(crate) fn read(reader: &mut BinaryReader) -> Result<Self, BlueprintError> {
+        reader.ensure(BlueprintDataHeader::SIZE)?;
+        let start = reader.offset();
+        let patch = reader.read_i32()?;
+        let cursor_offset_x = reader.read_i32()?;
+        let cursor_offset_y = reader.read_i32()?;
+        let cursor_target_area = reader.read_i32()?;
+        let dragbox_size_x = reader.read_i32()?;
+        let dragbox_size_y = reader.read_i32()?;
+        let primary_area_index = reader.read_i32()?;
+        let area_count = reader.read_i8()?;
+        if area_count < 0 {
+            return Err(BlueprintError::CorruptedHeader {
+                location: reader.location_at(start + 4 * 7),
+                reason: "area count is negative",
+            });
+        }
+        if primary_area_index < -1 || primary_area_index >= area_count as i32 {
+            return Err(BlueprintError::CorruptedHeader {
+                location: reader.location_at(start + 4 * 6),
+                reason: "primary area index is out of the area list",
+            });
+        }
+        Ok(BlueprintDataHeader {
+            patch,
+            cursor_offset_x,
+            cursor_offset_y,
+            cursor_target_area,
+            dragbox_size_x,
+            dragbox_size_y,
+            primary_area_index,
+            area_count,
+        })
+    }
+
+    /// Appends the binary representation of the header to `data`.
+    pub fn serialize(&self, data: &mut Vec<u8>) {
+        write_i32(data, self.patch);
+        write_i32(data, self.cursor_offset_x);
+        write_i32(data, self.cursor_offset_y);
+        write_i32(data, self.cursor_target_area);
+        write_i32(data, self.dragbox_size_x);
+        write_i32(data, self.dragbox_size_y);
+        write_i32(data, self.primary_area_index);
+        write_i8(data, self.area_count);
     }
 }
 
-#[derive(Debug, Serialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct BuildingHeader {
     pub building_count: i32,
 }
 
 impl BuildingHeader {
     pub const SIZE: usize = 4;
-    pub fn deserialize(
-        data: &[u8],
-        offset: usize,
-    ) -> Result<(Self, usize), Box<dyn std::error::Error>> {
-        if data.len() < BlueprintDataHeader::SIZE + offset {
-            return Err(InvalidDataCount(data.len()).into());
+
+    pub(crate) fn read(reader: &mut BinaryReader) -> Result<Self, BlueprintError> {
+        let building_count = reader.read_i32()?;
+        if building_count < 0 {
+            return Err(BlueprintError::CorruptedHeader {
+                location: reader.location_at(reader.offset() - 4),
+                reason: "building count is negative",
+            });
         }
-        let (building_count, offset) = read_i32(data, offset);
-        Ok((BuildingHeader { building_count }, offset))
+        Ok(BuildingHeader { building_count })
+    }
+
+    /// Appends the binary representation of the header to `data`.
+    pub fn serialize(&self, data: &mut Vec<u8>) {
+        write_i32(data, self.building_count);
     }
 }
 
@@ -90,25 +125,45 @@ pub struct BlueprintData {
 }
 
 impl BlueprintData {
+    /// Size in bytes of the serialized data.
     pub fn size(&self) -> usize {
-        todo!("Implement calculating size of BlueprintData")
+        BlueprintDataHeader::SIZE
+            + self.areas.len() * BlueprintArea::SIZE
+            + BuildingHeader::SIZE
+            + self.buildings.iter().map(|b| b.size()).sum::<usize>()
     }
 
-    pub fn deserialize(data: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
-        let (header, mut offset) = BlueprintDataHeader::deserialize(data, 0)?;
+    pub fn deserialize(data: &[u8]) -> Result<Self, BlueprintError> {
+        Self::deserialize_with(data, &DecodeOptions::strict()).map(|(bp_data, _)| bp_data)
+    }
+
+    /// Deserializes the data with `options`, returning what the decoder had to guess about.
+    pub fn deserialize_with(
+        data: &[u8],
+        options: &DecodeOptions,
+    ) -> Result<(Self, Vec<DecodeWarning>), BlueprintError> {
+        let mut warnings = Vec::new();
+        let mut reader = BinaryReader::new(data, 0);
+        let header = BlueprintDataHeader::read(&mut reader)?;
+        reader.set_section(Section::Areas);
         let mut areas = Vec::with_capacity(header.area_count as usize);
         for _ in 0..header.area_count {
-            let (area, new_offset) = BlueprintArea::deserialize(data, offset)?;
-            offset = new_offset;
-            areas.push(area);
-        }
-        let (b_header, new_offset) = BuildingHeader::deserialize(data, offset)?;
-        offset = new_offset;
-        let mut buildings = Vec::with_capacity(b_header.building_count as usize);
-        for _ in 0..b_header.building_count {
-            let (building, new_offset) = BlueprintBuilding::deserialize(data, offset)?;
-            offset = new_offset;
-            buildings.push(building);
+            areas.push(BlueprintArea::read(&mut reader)?);
+        }
+        reader.set_section(Section::BuildingHeader);
+        let b_header = BuildingHeader::read(&mut reader)?;
+        reader.set_section(Section::Buildings);
+        // the count comes from untrusted data, so don't let it reserve more than could be read
+        let capacity = (b_header.building_count as usize)
+            .min(reader.remaining() / BlueprintBuilding::MIN_SIZE);
+        let mut buildings = Vec::with_capacity(capacity);
+        for i in 0..b_header.building_count as usize {
+            reader.set_building(Some(i));
+            buildings.push(BlueprintBuilding::read(
+                &mut reader,
+                options,
+                &mut warnings,
+            )?);
         }
         let mut bp_data = BlueprintData {
             header,
@@ -116,7 +171,401 @@ impl BlueprintData {
             buildings,
         };
         bp_data.data_repair();
-        Ok(bp_data)
+        Ok((bp_data, warnings))
+    }
+
+    /// Deserializes as much of the data as possible, e.g. of a blueprint truncated by a chat app.
+    ///
+    /// Only a broken data header fails the whole decode. Otherwise every area and building read
+    /// before the failure is returned, along with where and why decoding stopped. With `resync`
+    /// a broken building record is skipped by looking for the next plausible record after it:
+    /// a known item in a valid area with an index above the previous building. Buildings left
+    /// out this way are still referenced by the connections of the others.
+    pub fn deserialize_partial(
+        data: &[u8],
+        options: &DecodeOptions,
+        resync: bool,
+    ) -> Result<PartialDecode, BlueprintError> {
+        let mut warnings = Vec::new();
+        let mut failures = Vec::new();
+        let mut reader = BinaryReader::new(data, 0);
+        let mut header = BlueprintDataHeader::read(&mut reader)?;
+        let expected_areas = header.area_count as usize;
+
+        reader.set_section(Section::Areas);
+        let mut areas = Vec::with_capacity(expected_areas);
+        let mut buildings = Vec::new();
+        let mut expected_buildings = 0;
+        let result = (|| {
+            for _ in 0..expected_areas {
+                areas.push(BlueprintArea::read(&mut reader)?);
+            }
+            reader.set_section(Section::BuildingHeader);
+            expected_buildings = BuildingHeader::read(&mut reader)?.building_count as usize;
+            reader.set_section(Section::Buildings);
+            // skipped bytes may have held several records, so after a resync the end of the data
+            // ends the building list too
+            while buildings.len() < expected_buildings
+                && (failures.is_empty() || reader.remaining() > 0)
+            {
+                reader.set_building(Some(buildings.len()));
+                let record_start = reader.offset();
+                match BlueprintBuilding::read(&mut reader, options, &mut warnings) {
+                    Ok(building) => buildings.push(building),
+                    Err(error) => {
+                        let next = match resync {
+                            true => Self::resync(
+                                data,
+                                record_start + 1,
+                                &buildings,
+                                &header,
+                                expected_buildings,
+                                options,
+                            ),
+                            false => None,
+                        };
+                        let Some((next_start, building, next_reader, next_warnings)) = next else {
+                            return Err(error);
+                        };
+                        failures.push(DecodeFailure {
+                            error,
+                            skipped: Some(record_start..next_start),
+                        });
+                        warnings.extend(next_warnings);
+                        buildings.push(building);
+                        reader = next_reader;
+                    }
+                }
+            }
+            Ok(())
+        })();
+        if let Err(error) = result {
+            failures.push(DecodeFailure {
+                error,
+                skipped: None,
+            });
+        }
+
+        header.area_count = areas.len() as i8;
+        let mut bp_data = BlueprintData {
+            header,
+            areas,
+            buildings,
+        };
+        bp_data.data_repair();
+        Ok(PartialDecode {
+            data: bp_data,
+            warnings,
+            failures,
+            expected_areas,
+            expected_buildings,
+        })
+    }
+
+    /// Finds the first plausible building record at or after `from`, returning its offset, the
+    /// building and the reader positioned after it.
+    fn resync<'a>(
+        data: &'a [u8],
+        from: usize,
+        decoded: &[BlueprintBuilding],
+        header: &BlueprintDataHeader,
+        building_count: usize,
+        options: &DecodeOptions,
+    ) -> Option<(
+        usize,
+        BlueprintBuilding,
+        BinaryReader<'a>,
+        Vec<DecodeWarning>,
+    )> {
+        let min_index = decoded.last().map_or(0, |b| b.index + 1);
+        (from..data.len()).find_map(|offset| {
+            let mut reader = BinaryReader::new(data, offset);
+            reader.set_section(Section::Buildings);
+            reader.set_building(Some(decoded.len()));
+            let mut warnings = Vec::new();
+            let building = BlueprintBuilding::read(&mut reader, options, &mut warnings).ok()?;
+            let plausible = (building.item().is_some() || building.shape.is_some())
+                && (0..header.area_count).contains(&building.area_index)
+                && building.index >= min_index
+                && (building.index as usize) < building_count;
+            plausible.then_some((offset, building, reader, warnings))
+        })
+    }
+
+    /// Changes every station with `update` and writes it back, returns the number of stations.
+    ///
+    /// Station ints which aren't decoded keep their values.
+    pub fn update_stations<F>(&mut self, mut update: F) -> Result<usize, BlueprintError>
+    where
+        F: FnMut(&mut StationParameters),
+    {
+        let mut count = 0;
+        for (i, building) in self.buildings.iter_mut().enumerate() {
+            if building
+                .update_station(&mut update)
+                .map_err(|e| e.in_building(i))?
+            {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Changes the label of every belt with `update`, returns the number of belts.
+    pub fn update_belts<F>(&mut self, mut update: F) -> usize
+    where
+        F: FnMut(&mut BeltParameters),
+    {
+        let mut count = 0;
+        for building in self.buildings.iter_mut() {
+            if let BlueprintBuildingParameters::Belt(mut belt) = building.get_parameters() {
+                update(&mut belt);
+                belt.write(&mut building.parameters);
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Points every EM-rail ejector to the swarm orbit `orbit_id`, returns the number of ejectors.
+    pub fn set_ejector_orbits(&mut self, orbit_id: i32) -> usize {
+        let ejector = EjectorParameters { orbit_id };
+        let mut count = 0;
+        for building in self.buildings.iter_mut() {
+            if let BlueprintBuildingParameters::Ejector(_) = building.get_parameters() {
+                ejector.write(&mut building.parameters);
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Where the sorter at `i` takes items from and drops them, `None` if it isn't a sorter.
+    pub fn sorter_connection(&self, i: usize) -> Option<SorterConnection> {
+        let sorter = self.buildings.get(i)?;
+        if !sorter.item().is_some_and(|item| item.is_sorter()) {
+            return None;
+        }
+        let positions = self.positions();
+        Some(SorterConnection::resolve(sorter, |index| {
+            positions.get(&index).copied()
+        }))
+    }
+
+    /// Indices of the sorters attached to a building which isn't in the blueprint, or moving
+    /// items between buildings whose recipes don't make or use them.
+    ///
+    /// The items a sorter moves are its filter, or the products of the recipe it takes from.
+    pub fn sorter_mismatches(&self) -> Vec<usize> {
+        let positions = self.positions();
+        let recipe = |end: &SorterEnd| {
+            let building = &self.buildings[end.building?];
+            match building.get_parameters() {
+                BlueprintBuildingParameters::Assembler(assembler) => assembler.recipe(),
+                _ => None,
+            }
+        };
+        let mut mismatches = Vec::new();
+        for (i, sorter) in self.buildings.iter().enumerate() {
+            let BlueprintBuildingParameters::Sorter(params) = sorter.get_parameters() else {
+                continue;
+            };
+            let connection =
+                SorterConnection::resolve(sorter, |index| positions.get(&index).copied());
+            if connection.pickup.is_dangling() || connection.drop.is_dangling() {
+                mismatches.push(i);
+                continue;
+            }
+            let pickup = recipe(&connection.pickup);
+            let moved: Vec<DysonSphereItem> = match (params.filter(), pickup) {
+                (Some(filter), Some(pickup)) => {
+                    if !pickup.outputs().iter().any(|(item, _)| *item == filter) {
+                        mismatches.push(i);
+                        continue;
+                    }
+                    vec![filter]
+                }
+                (Some(filter), None) => vec![filter],
+                (None, Some(pickup)) => pickup.outputs().iter().map(|(item, _)| *item).collect(),
+                (None, None) => continue,
+            };
+            if let Some(drop) = recipe(&connection.drop) {
+                if !drop.inputs().iter().any(|(item, _)| moved.contains(item)) {
+                    mismatches.push(i);
+                }
+            }
+        }
+        mismatches
+    }
+
+    /// Positions of the buildings by their `index`.
+    fn positions(&self) -> HashMap<i32, usize> {
+        self.buildings
+            .iter()
+            .enumerate()
+            .map(|(i, building)| (building.index, i))
+            .collect()
+    }
+
+    /// Indices of the buildings set to a recipe they can't run.
+    ///
+    /// Recipes missing from [`Recipe`](crate::Recipe) can't be checked, see [`BlueprintData::unknown_recipes`].
+    pub fn recipe_mismatches(&self) -> Vec<usize> {
+        self.buildings
+            .iter()
+            .enumerate()
+            .filter(|(_, building)| match building.get_parameters() {
+                BlueprintBuildingParameters::Assembler(assembler) => {
+                    assembler.recipe().is_some()
+                        && building.item().is_some_and(|item| !assembler.fits(item))
+                }
+                BlueprintBuildingParameters::Lab(lab) => lab.recipe().is_some() && !lab.fits(),
+                _ => false,
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Indices of the buildings set to a recipe missing from [`Recipe`](crate::Recipe), like the recipes added
+    /// with the Dark Fog. They aren't counted by [`BlueprintData::recipe_mismatches`].
+    pub fn unknown_recipes(&self) -> Vec<usize> {
+        self.buildings
+            .iter()
+            .enumerate()
+            .filter(|(_, building)| match building.get_parameters() {
+                BlueprintBuildingParameters::Assembler(assembler) => {
+                    assembler.recipe_id != 0 && assembler.recipe().is_none()
+                }
+                BlueprintBuildingParameters::Lab(lab) => {
+                    lab.mode == LabMode::Production && lab.recipe_id != 0 && lab.recipe().is_none()
+                }
+                _ => false,
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Stacks of labs, each listing the positions of its labs from the bottom up.
+    ///
+    /// A lab sits on top of the lab its `input_object_index` points at.
+    pub fn lab_stacks(&self) -> Vec<Vec<usize>> {
+        let is_lab =
+            |building: &BlueprintBuilding| building.item().is_some_and(LabParameters::is_lab);
+        let positions = self.positions();
+        let below = |building: &BlueprintBuilding| {
+            positions
+                .get(&building.input_object_index)
+                .copied()
+                .filter(|&i| is_lab(&self.buildings[i]))
+        };
+        let mut above: HashMap<usize, usize> = HashMap::new();
+        for (i, building) in self.buildings.iter().enumerate() {
+            if let Some(below) = below(building).filter(|_| is_lab(building)) {
+                above.insert(below, i);
+            }
+        }
+        let mut stacks = Vec::new();
+        for (i, building) in self.buildings.iter().enumerate() {
+            if !is_lab(building) || below(building).is_some() {
+                continue;
+            }
+            let mut stack = vec![i];
+            while let Some(&next) = above.get(stack.last().unwrap()) {
+                if stack.contains(&next) {
+                    break;
+                }
+                stack.push(next);
+            }
+            stacks.push(stack);
+        }
+        stacks
+    }
+
+    /// Writes `lab` into every lab of `stack`, so the stack works as one unit.
+    ///
+    /// Fails with [`BlueprintError::MissingBuilding`] if a position of `stack` is out of range.
+    pub fn set_lab_stack(
+        &mut self,
+        stack: &[usize],
+        lab: &LabParameters,
+    ) -> Result<(), BlueprintError> {
+        let parameters = BlueprintBuildingParameters::Lab(lab.clone());
+        let building_count = self.buildings.len();
+        for &i in stack {
+            self.buildings
+                .get_mut(i)
+                .ok_or(BlueprintError::MissingBuilding {
+                    position: i,
+                    building_count,
+                })?
+                .set_parameters(&parameters)
+                .map_err(|e| e.in_building(i))?;
+        }
+        Ok(())
+    }
+
+    /// Turns the stations `select` returns `true` for into stations of `kind`.
+    ///
+    /// Returns the number of converted stations, stations already of `kind` are left alone.
+    pub fn convert_stations<F>(
+        &mut self,
+        kind: StationKind,
+        mut select: F,
+    ) -> Result<usize, BlueprintError>
+    where
+        F: FnMut(&BlueprintBuilding) -> bool,
+    {
+        let mut count = 0;
+        for (i, building) in self.buildings.iter_mut().enumerate() {
+            if select(building)
+                && building
+                    .convert_station(kind)
+                    .map_err(|e| e.in_building(i))?
+            {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Serializes the data into the binary format accepted by the game.
+    ///
+    /// Every building is written with the record version it was read from.
+    /// `header.area_count` is written as is, so it has to match `areas.len()`.
+    pub fn serialize(&self) -> Result<Vec<u8>, BlueprintError> {
+        self.serialize_buildings_as(None)
+    }
+
+    /// Serializes the data writing every building with the `version` record layout.
+    ///
+    /// Fails if any of the buildings can't be written with `version` without losing data.
+    pub fn serialize_as(&self, version: BuildingVersion) -> Result<Vec<u8>, BlueprintError> {
+        self.serialize_buildings_as(Some(version))
+    }
+
+    fn serialize_buildings_as(
+        &self,
+        version: Option<BuildingVersion>,
+    ) -> Result<Vec<u8>, BlueprintError> {
+        if self.header.area_count as usize != self.areas.len() {
+            return Err(BlueprintError::InvalidCount {
+                what: "areas for the header area count",
+                count: self.areas.len(),
+            });
+        }
+        let mut data = Vec::with_capacity(self.size());
+        self.header.serialize(&mut data);
+        for area in self.areas.iter() {
+            area.serialize(&mut data);
+        }
+        BuildingHeader {
+            building_count: self.buildings.len() as i32,
+        }
+        .serialize(&mut data);
+        for building in self.buildings.iter() {
+            building.serialize_as(&mut data, version.unwrap_or(building.version))?;
+        }
+        Ok(data)
     }
 
     /// something from DSP decompiled code
@@ -140,6 +589,267 @@ impl BlueprintData {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::recipe::Recipe;
+    use crate::entities::signal::Signal;
+
+    /// Blueprint of one area holding `buildings`.
+    fn blueprint(buildings: Vec<BlueprintBuilding>) -> BlueprintData {
+        BlueprintData {
+            header: BlueprintDataHeader {
+                patch: 1,
+                cursor_offset_x: 0,
+                cursor_offset_y: 0,
+                cursor_target_area: 0,
+                dragbox_size_x: 1,
+                dragbox_size_y: 1,
+                primary_area_index: 0,
+                area_count: 1,
+            },
+            areas: vec![BlueprintArea {
+                index: 0,
+                parent_index: -1,
+                tropic_anchor: 0,
+                area_segments: 200,
+                anchor_local_offset_x: 0,
+                anchor_local_offset_y: 0,
+                width: 1,
+                height: 1,
+            }],
+            buildings,
+        }
+    }
+
+    #[test]
+    fn test_update_stations() {
+        let smelter = BlueprintBuilding::new(0, DysonSphereItem::Smelter);
+        let mut planetary = BlueprintBuilding::new(1, DysonSphereItem::PlanetaryLogisticsStation);
+        planetary.parameters = vec![0; 2048];
+        let mut interstellar =
+            BlueprintBuilding::new(2, DysonSphereItem::InterstellarLogisticsStation);
+        interstellar.parameters = vec![0; 2048];
+        interstellar.parameters[2000] = 7;
+        let mut data = blueprint(vec![smelter.clone(), planetary, interstellar]);
+
+        let count = data
+            .update_stations(|station| station.parameters.drone_count = 50)
+            .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(data.buildings[0], smelter);
+        assert_eq!(data.buildings[1].parameters[320 + 6], 50);
+        assert_eq!(data.buildings[2].parameters[320 + 6], 50);
+        assert_eq!(data.buildings[2].parameters[2000], 7);
+        let reparsed = BlueprintData::deserialize(&data.serialize().unwrap()).unwrap();
+        assert_eq!(reparsed, data);
+
+        match data.update_stations(|station| station.storage.resize(40, None)) {
+            Err(BlueprintError::StationLayout { building, .. }) => assert_eq!(building, Some(1)),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_convert_stations() {
+        let mut planetary = BlueprintBuilding::new(0, DysonSphereItem::PlanetaryLogisticsStation);
+        planetary.model_index = 49;
+        planetary.parameters = vec![0; 2048];
+        let mut interstellar =
+            BlueprintBuilding::new(1, DysonSphereItem::InterstellarLogisticsStation);
+        interstellar.model_index = 50;
+        interstellar.parameters = vec![0; 2048];
+        let mut kept = interstellar.clone();
+        kept.index = 2;
+        let mut data = blueprint(vec![planetary, interstellar, kept]);
+
+        let count = data
+            .convert_stations(StationKind::Planetary, |building| building.index != 2)
+            .unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(data.buildings[1].model_index, 49);
+        assert_eq!(data.buildings[2].model_index, 50);
+
+        let count = data
+            .convert_stations(StationKind::Interstellar, |_| true)
+            .unwrap();
+        assert_eq!(count, 2);
+        let reparsed = BlueprintData::deserialize(&data.serialize().unwrap()).unwrap();
+        assert_eq!(reparsed, data);
+        assert!(reparsed
+            .buildings
+            .iter()
+            .all(|b| b.item() == Some(DysonSphereItem::InterstellarLogisticsStation)));
+    }
+
+    #[test]
+    fn test_recipe_mismatches() {
+        let machine = |index: i32, item: DysonSphereItem, recipe_id: i16| {
+            let mut building = BlueprintBuilding::new(index, item);
+            building.recipe_id = recipe_id;
+            building
+        };
+        let data = blueprint(vec![
+            machine(0, DysonSphereItem::Smelter, Recipe::IronIngot as i16),
+            machine(1, DysonSphereItem::Smelter, Recipe::CircuitBoard as i16),
+            machine(2, DysonSphereItem::AssemblingMachineMkII, 0),
+            machine(3, DysonSphereItem::AssemblingMachineMkII, 105),
+            machine(4, DysonSphereItem::ChemicalPlant, Recipe::Graphene as i16),
+            machine(5, DysonSphereItem::ConveyorBeltMKI, Recipe::Gear as i16),
+        ]);
+        assert_eq!(data.recipe_mismatches(), [1]);
+        assert_eq!(data.unknown_recipes(), [3]);
+    }
+
+    #[test]
+    fn test_update_belts() {
+        let mut labeled = BlueprintBuilding::new(1, DysonSphereItem::ConveyorBeltMKIII);
+        labeled.parameters = vec![DysonSphereItem::IronIngot as i32, 2];
+        let mut sorter = BlueprintBuilding::new(2, DysonSphereItem::SorterMKI);
+        sorter.parameters = vec![1];
+        let mut data = blueprint(vec![
+            BlueprintBuilding::new(0, DysonSphereItem::ConveyorBeltMKIII),
+            labeled,
+            sorter,
+        ]);
+
+        let count = data.update_belts(|belt| {
+            if belt.icon.item() == Some(DysonSphereItem::IronIngot) {
+                belt.icon = Signal::from(DysonSphereItem::Steel);
+            }
+        });
+        assert_eq!(count, 2);
+        assert!(data.buildings[0].parameters.is_empty());
+        assert_eq!(
+            data.buildings[1].parameters,
+            [DysonSphereItem::Steel as i32, 2]
+        );
+        assert_eq!(data.buildings[2].parameters, [1]);
+    }
+
+    #[test]
+    fn test_sorter_mismatches() {
+        let machine = |index: i32, item: DysonSphereItem, recipe: Recipe| {
+            let mut building = BlueprintBuilding::new(index, item);
+            building.recipe_id = recipe as i16;
+            building
+        };
+        let sorter = |index: i32, from: i32, to: i32, filter: i16| {
+            let mut building = BlueprintBuilding::new(index, DysonSphereItem::SorterMKI);
+            building.input_object_index = from;
+            building.output_object_index = to;
+            building.input_from_slot = 1;
+            building.output_to_slot = 2;
+            building.filter_id = filter;
+            building.parameters = vec![1];
+            building
+        };
+        let data = blueprint(vec![
+            machine(10, DysonSphereItem::Smelter, Recipe::IronIngot),
+            machine(11, DysonSphereItem::AssemblingMachineMkI, Recipe::Gear),
+            machine(
+                12,
+                DysonSphereItem::AssemblingMachineMkI,
+                Recipe::CircuitBoard,
+            ),
+            sorter(20, 10, 11, 0),
+            sorter(21, 11, 12, 0),
+            sorter(22, 99, 11, 0),
+            sorter(23, 10, 12, DysonSphereItem::CopperIngot as i16),
+            sorter(24, -1, -1, 0),
+            sorter(25, 10, 12, DysonSphereItem::IronIngot as i16),
+        ]);
+
+        let connection = data.sorter_connection(3).unwrap();
+        assert_eq!(connection.pickup.object_index, Some(10));
+        assert_eq!(connection.pickup.building, Some(0));
+        assert_eq!(connection.pickup.slot, 1);
+        assert_eq!(connection.drop.building, Some(1));
+        assert_eq!(connection.drop.slot, 2);
+        assert!(data.sorter_connection(5).unwrap().pickup.is_dangling());
+        assert_eq!(data.sorter_connection(7).unwrap().drop.object_index, None);
+        assert_eq!(data.sorter_connection(0), None);
+
+        assert_eq!(data.sorter_mismatches(), [4, 5, 6]);
+    }
+
+    #[test]
+    fn test_lab_stacks() {
+        let lab = |index: i32, below: i32| {
+            let mut building = BlueprintBuilding::new(index, DysonSphereItem::MatrixLab);
+            building.input_object_index = below;
+            building.recipe_id = Recipe::ElectromagneticMatrix as i16;
+            building.parameters = vec![1];
+            building
+        };
+        // a smelter under a lab doesn't make it part of a stack
+        let smelter = BlueprintBuilding::new(14, DysonSphereItem::Smelter);
+        let mut data = blueprint(vec![
+            lab(12, 11),
+            lab(10, -1),
+            lab(13, 14),
+            lab(11, 10),
+            smelter,
+        ]);
+        assert_eq!(data.lab_stacks(), [vec![1, 3, 0], vec![2]]);
+        assert!(data.recipe_mismatches().is_empty());
+
+        let mut research = LabParameters::default();
+        research.set_research(1101);
+        data.set_lab_stack(&[1, 3, 0], &research).unwrap();
+        for i in [0, 1, 3] {
+            assert_eq!(data.buildings[i].recipe_id, 0);
+            assert_eq!(data.buildings[i].parameters, [2, 1101]);
+        }
+        let BlueprintBuildingParameters::Lab(untouched) = data.buildings[2].get_parameters() else {
+            panic!("lab isn't decoded");
+        };
+        assert_eq!(untouched.mode, LabMode::Production);
+
+        match data.set_lab_stack(&[2, 5], &research) {
+            Err(BlueprintError::MissingBuilding {
+                position,
+                building_count,
+            }) => assert_eq!((position, building_count), (5, 5)),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_lab_recipes() {
+        let lab = |index: i32, parameters: Vec<i32>, recipe_id: i16| {
+            let mut building = BlueprintBuilding::new(index, DysonSphereItem::MatrixLab);
+            building.recipe_id = recipe_id;
+            building.parameters = parameters;
+            building
+        };
+        let data = blueprint(vec![
+            lab(0, vec![1], Recipe::EnergyMatrix as i16),
+            lab(1, vec![1], Recipe::Gear as i16),
+            lab(2, vec![1], 105),
+            lab(3, vec![1], 0),
+            lab(4, vec![2, 1101], 0),
+        ]);
+        assert_eq!(data.recipe_mismatches(), [1]);
+        assert_eq!(data.unknown_recipes(), [2]);
+    }
+
+    #[test]
+    fn test_set_ejector_orbits() {
+        let mut ejector = BlueprintBuilding::new(0, DysonSphereItem::EMRailEjector);
+        ejector.parameters = vec![1];
+        let mut silo = BlueprintBuilding::new(1, DysonSphereItem::VerticalLaunchingSilo);
+        silo.parameters = vec![1];
+        let unset = BlueprintBuilding::new(2, DysonSphereItem::EMRailEjector);
+        let mut data = blueprint(vec![ejector, silo, unset]);
+
+        assert_eq!(data.set_ejector_orbits(4), 2);
+        assert_eq!(data.buildings[0].parameters, [4]);
+        assert_eq!(data.buildings[1].parameters, [1]);
+        assert_eq!(data.buildings[2].parameters, [4]);
+    }
+}
+
 #[cfg(FALSE)]
 #[cfg(test)]
 mod tests {
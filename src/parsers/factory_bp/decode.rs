@@ -0,0 +1,125 @@
+/*
+ * Copyright (c) 2025. sakost aka Konstantin Sazhenov
+ * All rights reserved.
+ */
+
+use crate::errors::{BlueprintError, Location};
+use crate::parsers::factory_bp::building::BuildingShape;
+use crate::parsers::factory_bp::data::BlueprintData;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// How the decoder reacts to buildings it can't fully interpret.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum DecodeMode {
+    /// Any unknown item aborts decoding, useful for validation.
+    #[default]
+    Strict,
+    /// Records of unknown items are decoded with an assumed shape and reported as warnings.
+    Lenient,
+}
+
+/// Options of [`BlueprintData::deserialize_with`](crate::BlueprintData::deserialize_with).
+#[derive(Debug, Clone, Default)]
+pub struct DecodeOptions {
+    pub mode: DecodeMode,
+    /// Shapes to assume for unknown item IDs in lenient mode. Items without a hint are decoded
+    /// as [`BuildingShape::Other`], which is how every building that is neither a belt nor a
+    /// sorter is stored.
+    pub shape_hints: HashMap<i16, BuildingShape>,
+}
+
+impl DecodeOptions {
+    pub fn strict() -> Self {
+        DecodeOptions::default()
+    }
+
+    pub fn lenient() -> Self {
+        DecodeOptions {
+            mode: DecodeMode::Lenient,
+            ..DecodeOptions::default()
+        }
+    }
+
+    /// Assumes `shape` for records of the unknown item `item_id`.
+    pub fn with_shape_hint(mut self, item_id: i16, shape: BuildingShape) -> Self {
+        self.shape_hints.insert(item_id, shape);
+        self
+    }
+
+    /// Shape to decode the unknown item with, `None` in strict mode.
+    pub(crate) fn unknown_item_shape(&self, item_id: i16) -> Option<BuildingShape> {
+        match self.mode {
+            DecodeMode::Strict => None,
+            DecodeMode::Lenient => Some(
+                self.shape_hints
+                    .get(&item_id)
+                    .copied()
+                    .unwrap_or(BuildingShape::Other),
+            ),
+        }
+    }
+}
+
+/// Something the lenient decoder had to guess about.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeWarning {
+    /// A -101 or -102 record of an item missing from [`DysonSphereItem`](crate::DysonSphereItem)
+    /// was decoded assuming `shape`.
+    UnknownItem {
+        id: i32,
+        location: Location,
+        shape: BuildingShape,
+    },
+}
+
+impl std::fmt::Display for DecodeWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DecodeWarning::UnknownItem {
+                id,
+                location,
+                shape,
+            } => write!(
+                f,
+                "unknown item {} in {} decoded as {:?}",
+                id, location, shape
+            ),
+        }
+    }
+}
+
+/// Where and why [`BlueprintData::deserialize_partial`] couldn't decode the data.
+#[derive(Debug)]
+pub struct DecodeFailure {
+    pub error: BlueprintError,
+    /// Bytes skipped to get to the next plausible building record, `None` if decoding stopped.
+    pub skipped: Option<Range<usize>>,
+}
+
+/// Everything [`BlueprintData::deserialize_partial`] managed to decode.
+#[derive(Debug)]
+pub struct PartialDecode {
+    /// Decoded header, areas and buildings. `header.area_count` is set to the number of decoded
+    /// areas, so the data can be serialized again.
+    pub data: BlueprintData,
+    pub warnings: Vec<DecodeWarning>,
+    /// Failures in the order they were met, only the last one can have stopped decoding.
+    pub failures: Vec<DecodeFailure>,
+    /// Area count declared by the data header.
+    pub expected_areas: usize,
+    /// Building count declared by the building header, 0 if it couldn't be read.
+    pub expected_buildings: usize,
+}
+
+impl PartialDecode {
+    /// Whether the data was decoded without any failure.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Failure decoding stopped at, `None` if it reached the end of the data.
+    pub fn stopped_at(&self) -> Option<&DecodeFailure> {
+        self.failures.last().filter(|f| f.skipped.is_none())
+    }
+}
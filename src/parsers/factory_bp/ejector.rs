@@ -0,0 +1,54 @@
+/*
+ * Copyright (c) 2025. sakost aka Konstantin Sazhenov
+ * All rights reserved.
+ */
+
+use crate::parsers::factory_bp::utils::write_param;
+use serde::{Deserialize, Serialize};
+
+/// Settings of an EM-rail ejector, the first parameter is the swarm orbit it shoots sails to.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct EjectorParameters {
+    /// Dyson swarm orbit ID, `0` if no orbit is chosen.
+    pub orbit_id: i32,
+}
+
+impl EjectorParameters {
+    const ORBIT_ID: usize = 0;
+
+    pub fn new(params: &[i32]) -> Self {
+        EjectorParameters {
+            orbit_id: params.get(Self::ORBIT_ID).copied().unwrap_or(0),
+        }
+    }
+
+    /// Writes the orbit into `params`, which is only grown for a chosen orbit.
+    pub fn write(&self, params: &mut Vec<i32>) {
+        write_param(params, Self::ORBIT_ID, self.orbit_id, 0);
+    }
+
+    /// Serializing to JSON-string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ejector() {
+        let ejector = EjectorParameters::new(&[3, 7]);
+        assert_eq!(ejector.orbit_id, 3);
+        assert_eq!(EjectorParameters::new(&[]), EjectorParameters::default());
+
+        let mut params = vec![3, 7];
+        EjectorParameters { orbit_id: 5 }.write(&mut params);
+        assert_eq!(params, [5, 7]);
+
+        let mut empty = Vec::new();
+        EjectorParameters::default().write(&mut empty);
+        assert!(empty.is_empty());
+    }
+}
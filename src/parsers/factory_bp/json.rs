@@ -0,0 +1,288 @@
+/*
+ * Copyright (c) 2025. sakost aka Konstantin Sazhenov
+ * All rights reserved.
+ */
+
+use crate::errors::BlueprintError;
+use crate::parsers::factory_bp::building::{
+    BlueprintBuilding, BlueprintBuildingParameters, BuildingShape, BuildingVersion,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Serde representation of [`BlueprintBuilding`].
+///
+/// Parameters are typed whenever the building has a known layout. Ints the typed parameters
+/// don't reproduce go to `unmodeled_parameters`, so importing the JSON gives back the exact
+/// array it was exported from. Typed parameters carrying a filter or recipe must agree with
+/// `filter_id` and `recipe_id`, otherwise the import fails.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct BuildingJson {
+    index: i32,
+    area_index: i8,
+    local_offset_x: f32,
+    local_offset_y: f32,
+    local_offset_z: f32,
+    local_offset_x2: f32,
+    local_offset_y2: f32,
+    local_offset_z2: f32,
+    yaw: f32,
+    yaw2: f32,
+    item_id: i16,
+    model_index: i16,
+    output_object_index: i32,
+    input_object_index: i32,
+    output_to_slot: i8,
+    input_from_slot: i8,
+    output_from_slot: i8,
+    input_to_slot: i8,
+    output_offset: i8,
+    input_offset: i8,
+    recipe_id: i16,
+    filter_id: i16,
+    tilt: f32,
+    tilt2: f32,
+    pitch: f32,
+    pitch2: f32,
+    parameters: ParametersJson,
+    /// Length of the parameter array, only for typed parameters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parameter_count: Option<usize>,
+    /// Ints of the array the typed parameters don't reproduce, written over them on import.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    unmodeled_parameters: BTreeMap<usize, i32>,
+    content: String,
+    #[serde(default)]
+    version: BuildingVersion,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    shape: Option<BuildingShape>,
+}
+
+/// Typed parameters, or the plain array written before they were typed.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum ParametersJson {
+    Typed(BlueprintBuildingParameters),
+    Array(Vec<i32>),
+}
+
+impl From<BlueprintBuilding> for BuildingJson {
+    fn from(building: BlueprintBuilding) -> Self {
+        let typed = building.get_parameters();
+        let mut parameter_count = None;
+        let mut unmodeled_parameters = BTreeMap::new();
+        let parameters = if matches!(typed, BlueprintBuildingParameters::Raw(_)) {
+            ParametersJson::Typed(typed)
+        } else {
+            let mut written = vec![0; building.parameters.len()];
+            match typed.write(&mut written) {
+                Ok(()) => {
+                    parameter_count = Some(written.len());
+                    unmodeled_parameters = building
+                        .parameters
+                        .iter()
+                        .zip(written.iter())
+                        .enumerate()
+                        .filter(|(_, (original, written))| original != written)
+                        .map(|(i, (original, _))| (i, *original))
+                        .collect();
+                    ParametersJson::Typed(typed)
+                }
+                // parameters that can't be written back are kept as the plain array
+                Err(_) => ParametersJson::Array(building.parameters.clone()),
+            }
+        };
+        BuildingJson {
+            index: building.index,
+            area_index: building.area_index,
+            local_offset_x: building.local_offset_x,
+            local_offset_y: building.local_offset_y,
+            local_offset_z: building.local_offset_z,
+            local_offset_x2: building.local_offset_x2,
+            local_offset_y2: building.local_offset_y2,
+            local_offset_z2: building.local_offset_z2,
+            yaw: building.yaw,
+            yaw2: building.yaw2,
+            item_id: building.item_id,
+            model_index: building.model_index,
+            output_object_index: building.output_object_index,
+            input_object_index: building.input_object_index,
+            output_to_slot: building.output_to_slot,
+            input_from_slot: building.input_from_slot,
+            output_from_slot: building.output_from_slot,
+            input_to_slot: building.input_to_slot,
+            output_offset: building.output_offset,
+            input_offset: building.input_offset,
+            recipe_id: building.recipe_id,
+            filter_id: building.filter_id,
+            tilt: building.tilt,
+            tilt2: building.tilt2,
+            pitch: building.pitch,
+            pitch2: building.pitch2,
+            parameters,
+            parameter_count,
+            unmodeled_parameters,
+            content: building.content,
+            version: building.version,
+            shape: building.shape,
+        }
+    }
+}
+
+impl TryFrom<BuildingJson> for BlueprintBuilding {
+    type Error = BlueprintError;
+
+    fn try_from(json: BuildingJson) -> Result<Self, Self::Error> {
+        let parameters = match json.parameters {
+            ParametersJson::Array(parameters) => parameters,
+            ParametersJson::Typed(typed) => {
+                let conflict = |field, value: i16, parameters: Option<i16>| match parameters {
+                    Some(parameters) if parameters != value => {
+                        Err(BlueprintError::ConflictingBuildingField {
+                            building_index: json.index,
+                            field,
+                            value,
+                            parameters,
+                        })
+                    }
+                    _ => Ok(()),
+                };
+                conflict("filter_id", json.filter_id, typed.filter_id())?;
+                conflict("recipe_id", json.recipe_id, typed.recipe_id())?;
+                let mut parameters = vec![0; json.parameter_count.unwrap_or(0)];
+                typed.write(&mut parameters)?;
+                for (i, value) in json.unmodeled_parameters {
+                    if i >= parameters.len() {
+                        parameters.resize(i + 1, 0);
+                    }
+                    parameters[i] = value;
+                }
+                parameters
+            }
+        };
+        Ok(BlueprintBuilding {
+            index: json.index,
+            area_index: json.area_index,
+            local_offset_x: json.local_offset_x,
+            local_offset_y: json.local_offset_y,
+            local_offset_z: json.local_offset_z,
+            local_offset_x2: json.local_offset_x2,
+            local_offset_y2: json.local_offset_y2,
+            local_offset_z2: json.local_offset_z2,
+            yaw: json.yaw,
+            yaw2: json.yaw2,
+            item_id: json.item_id,
+            model_index: json.model_index,
+            output_object_index: json.output_object_index,
+            input_object_index: json.input_object_index,
+            output_to_slot: json.output_to_slot,
+            input_from_slot: json.input_from_slot,
+            output_from_slot: json.output_from_slot,
+            input_to_slot: json.input_to_slot,
+            output_offset: json.output_offset,
+            input_offset: json.input_offset,
+            recipe_id: json.recipe_id,
+            filter_id: json.filter_id,
+            tilt: json.tilt,
+            tilt2: json.tilt2,
+            pitch: json.pitch,
+            pitch2: json.pitch2,
+            parameters,
+            content: json.content,
+            version: json.version,
+            shape: json.shape,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::item::DysonSphereItem;
+    use crate::entities::recipe::Recipe;
+
+    #[test]
+    fn test_station_keeps_unmodeled_parameters() {
+        let mut station = BlueprintBuilding::new(1, DysonSphereItem::InterstellarLogisticsStation);
+        station.parameters = (0..2048).map(|i| i % 5).collect();
+        let json = serde_json::to_value(&station).unwrap();
+        assert!(json["parameters"]["Station"]["storage"].is_array());
+        assert_eq!(json["parameter_count"], 2048);
+        // past the station block and the ints it can't represent, like flags other than 0 and 1
+        assert!(json["unmodeled_parameters"].get("400").is_none());
+        assert_eq!(json["unmodeled_parameters"]["401"], 1);
+        assert_eq!(json["unmodeled_parameters"]["323"], 3);
+        assert_eq!(
+            serde_json::from_value::<BlueprintBuilding>(json).unwrap(),
+            station
+        );
+
+        // typed parameters are written over the unmodeled ones on import
+        let mut json = serde_json::to_value(&station).unwrap();
+        json["parameters"]["Station"]["parameters"]["drone_count"] = 50.into();
+        let imported: BlueprintBuilding = serde_json::from_value(json).unwrap();
+        assert_eq!(imported.parameters[326], 50);
+        assert_eq!(imported.parameters.len(), 2048);
+    }
+
+    #[test]
+    fn test_raw_parameters() {
+        let mut foundation = BlueprintBuilding::new(2, DysonSphereItem::Foundation);
+        foundation.parameters = vec![1, 2, 3];
+        let mut json = serde_json::to_value(&foundation).unwrap();
+        assert_eq!(json["parameters"]["Raw"], serde_json::json!([1, 2, 3]));
+        assert!(json.get("parameter_count").is_none());
+
+        // JSON from before parameters were typed
+        json["parameters"] = serde_json::json!([4, 5, 6]);
+        let imported: BlueprintBuilding = serde_json::from_value(json).unwrap();
+        assert_eq!(imported.parameters, [4, 5, 6]);
+    }
+
+    #[test]
+    fn test_short_typed_parameters() {
+        let mut tank = BlueprintBuilding::new(5, DysonSphereItem::StorageTank);
+        tank.parameters = vec![1];
+        let mut json = serde_json::to_value(&tank).unwrap();
+        assert_eq!(json["parameters"]["Tank"]["output"], true);
+        assert_eq!(json["parameter_count"], 1);
+
+        json["parameters"]["Tank"]["output"] = false.into();
+        let imported: BlueprintBuilding = serde_json::from_value(json).unwrap();
+        assert_eq!(imported.parameters, [1, 0]);
+    }
+
+    #[test]
+    fn test_splitter_filter() {
+        let mut splitter = BlueprintBuilding::new(3, DysonSphereItem::Splitter);
+        splitter.filter_id = DysonSphereItem::CopperIngot as i16;
+        splitter.parameters = vec![0, 0, 2];
+        let mut json = serde_json::to_value(&splitter).unwrap();
+        assert_eq!(json["parameter_count"], 3);
+        json["parameters"]["Splitter"]["filter_id"] = 0.into();
+        json["parameters"]["Splitter"]["box_top"] = true.into();
+        let err = serde_json::from_value::<BlueprintBuilding>(json.clone()).unwrap_err();
+        assert!(err.to_string().contains("filter_id"), "{}", err);
+
+        json["filter_id"] = 0.into();
+        let imported: BlueprintBuilding = serde_json::from_value(json).unwrap();
+        assert_eq!(imported.filter_id, 0);
+        assert_eq!(imported.parameters, [0, 0, 2, 0, 1]);
+    }
+
+    #[test]
+    fn test_assembler_recipe() {
+        let mut assembler = BlueprintBuilding::new(4, DysonSphereItem::AssemblingMachineMkII);
+        assembler.recipe_id = Recipe::Processor as i16;
+        assembler.parameters = vec![1];
+        let mut json = serde_json::to_value(&assembler).unwrap();
+        json["recipe_id"] = (Recipe::Gear as i16).into();
+        let err = serde_json::from_value::<BlueprintBuilding>(json.clone()).unwrap_err();
+        assert!(err.to_string().contains("recipe_id"), "{}", err);
+
+        json["parameters"]["Assembler"]["recipe_id"] = (Recipe::Gear as i16).into();
+        let imported: BlueprintBuilding = serde_json::from_value(json).unwrap();
+        assert_eq!(imported.recipe_id, Recipe::Gear as i16);
+        assert_eq!(imported.parameters, [1]);
+    }
+}
@@ -0,0 +1,153 @@
+/*
+ * Copyright (c) 2025. sakost aka Konstantin Sazhenov
+ * All rights reserved.
+ */
+
+use crate::entities::item::DysonSphereItem;
+use crate::entities::recipe::{Recipe, RecipeClass};
+use crate::parsers::factory_bp::utils::write_param;
+use serde::{Deserialize, Serialize};
+
+/// What a lab is used for.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Copy, Clone, Default)]
+pub enum LabMode {
+    #[default]
+    None,
+    /// Makes matrices with the recipe of the lab.
+    Production,
+    /// Researches the technology of the lab.
+    Research,
+    Unknown(i32),
+}
+
+impl From<i32> for LabMode {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => LabMode::None,
+            1 => LabMode::Production,
+            2 => LabMode::Research,
+            other => LabMode::Unknown(other),
+        }
+    }
+}
+
+impl From<LabMode> for i32 {
+    fn from(value: LabMode) -> Self {
+        match value {
+            LabMode::None => 0,
+            LabMode::Production => 1,
+            LabMode::Research => 2,
+            LabMode::Unknown(other) => other,
+        }
+    }
+}
+
+/// Settings of a lab, kept in `parameters` and `recipe_id`.
+///
+/// The first parameter is the mode, the second the researched technology. Missing parameters
+/// are taken as `0`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct LabParameters {
+    pub mode: LabMode,
+    /// Recipe made in production mode, `0` if there's none.
+    pub recipe_id: i16,
+    /// Technology researched in research mode, `0` if there's none.
+    pub tech_id: i32,
+}
+
+impl LabParameters {
+    const MODE: usize = 0;
+    const TECH_ID: usize = 1;
+
+    /// Whether `item` is a lab.
+    pub fn is_lab(item: DysonSphereItem) -> bool {
+        matches!(
+            item,
+            DysonSphereItem::MatrixLab | DysonSphereItem::SelfEvolutionLab
+        )
+    }
+
+    pub fn new(params: &[i32], recipe_id: i16) -> Self {
+        let param = |i: usize| params.get(i).copied().unwrap_or(0);
+        LabParameters {
+            mode: LabMode::from(param(Self::MODE)),
+            recipe_id,
+            tech_id: param(Self::TECH_ID),
+        }
+    }
+
+    /// Recipe made in production mode, `None` if there's none or it's unknown.
+    pub fn recipe(&self) -> Option<Recipe> {
+        Recipe::try_from(self.recipe_id as i32).ok()
+    }
+
+    /// Switches the lab to making matrices with `recipe`.
+    pub fn set_production(&mut self, recipe: Recipe) {
+        self.mode = LabMode::Production;
+        self.recipe_id = recipe as i16;
+        self.tech_id = 0;
+    }
+
+    /// Switches the lab to researching the technology `tech_id`.
+    pub fn set_research(&mut self, tech_id: i32) {
+        self.mode = LabMode::Research;
+        self.recipe_id = 0;
+        self.tech_id = tech_id;
+    }
+
+    /// Whether the recipe can be made in a lab, `true` outside of production mode or without a
+    /// recipe. Unknown recipes never fit.
+    pub fn fits(&self) -> bool {
+        self.mode != LabMode::Production
+            || self.recipe_id == 0
+            || self
+                .recipe()
+                .is_some_and(|recipe| recipe.class() == RecipeClass::Research)
+    }
+
+    /// Writes the settings into `params`, the recipe goes to `recipe_id` of the building.
+    ///
+    /// `params` is only grown for settings that aren't `0`.
+    pub fn write(&self, params: &mut Vec<i32>) {
+        write_param(params, Self::MODE, self.mode.into(), 0);
+        write_param(params, Self::TECH_ID, self.tech_id, 0);
+    }
+
+    /// Serializing to JSON-string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lab() {
+        let mut lab = LabParameters::new(&[1], Recipe::EnergyMatrix as i16);
+        assert_eq!(lab.mode, LabMode::Production);
+        assert_eq!(lab.recipe(), Some(Recipe::EnergyMatrix));
+        assert!(lab.fits());
+
+        lab.set_research(1001);
+        let mut params = vec![1];
+        lab.write(&mut params);
+        assert_eq!(params, [2, 1001]);
+        assert_eq!(lab.recipe_id, 0);
+        assert_eq!(LabParameters::new(&params, 0), lab);
+
+        lab.set_production(Recipe::Gear);
+        assert!(!lab.fits());
+        lab.recipe_id = 105;
+        assert!(!lab.fits());
+        lab.recipe_id = 0;
+        assert!(lab.fits());
+        lab.write(&mut params);
+        assert_eq!(params, [1, 0]);
+
+        let mut empty = Vec::new();
+        LabParameters::default().write(&mut empty);
+        assert!(empty.is_empty());
+    }
+}
@@ -3,10 +3,19 @@
  * All rights reserved.
  */
 
-pub(crate) mod area;
-pub(crate) mod blueprint;
-pub(crate) mod building;
-pub(crate) mod data;
-pub(crate) mod splitter;
-pub(crate) mod station;
+pub mod area;
+pub mod assembler;
+pub mod belt;
+pub mod blueprint;
+pub mod building;
+pub mod data;
+pub mod decode;
+pub mod ejector;
+pub(crate) mod json;
+pub mod lab;
+pub mod power;
+pub mod sorter;
+pub mod splitter;
+pub mod station;
+pub mod storage;
 pub(crate) mod utils;
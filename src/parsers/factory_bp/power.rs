@@ -0,0 +1,214 @@
+/*
+ * Copyright (c) 2025. sakost aka Konstantin Sazhenov
+ * All rights reserved.
+ */
+
+use crate::entities::item::DysonSphereItem;
+use crate::parsers::factory_bp::utils::write_param;
+use serde::{Deserialize, Serialize};
+
+/// What a ray receiver turns the Dyson sphere rays into.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Copy, Clone, Default)]
+pub enum RayReceiverMode {
+    #[default]
+    Power,
+    CriticalPhotons,
+    Unknown(i32),
+}
+
+impl From<i32> for RayReceiverMode {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => RayReceiverMode::Power,
+            v if v == DysonSphereItem::CriticalPhoton as i32 => RayReceiverMode::CriticalPhotons,
+            other => RayReceiverMode::Unknown(other),
+        }
+    }
+}
+
+impl From<RayReceiverMode> for i32 {
+    fn from(value: RayReceiverMode) -> Self {
+        match value {
+            RayReceiverMode::Power => 0,
+            RayReceiverMode::CriticalPhotons => DysonSphereItem::CriticalPhoton as i32,
+            RayReceiverMode::Unknown(other) => other,
+        }
+    }
+}
+
+/// Settings of a ray receiver.
+///
+/// The first parameter is the product, `0` for power, the second whether graviton lenses are
+/// used. Missing parameters are taken as `0`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct RayReceiverParameters {
+    pub mode: RayReceiverMode,
+    pub use_lens: bool,
+}
+
+impl RayReceiverParameters {
+    const MODE: usize = 0;
+    const USE_LENS: usize = 1;
+
+    pub fn new(params: &[i32]) -> Self {
+        let param = |i: usize| params.get(i).copied().unwrap_or(0);
+        RayReceiverParameters {
+            mode: RayReceiverMode::from(param(Self::MODE)),
+            use_lens: param(Self::USE_LENS) == 1,
+        }
+    }
+
+    /// Writes the settings into `params`, which is only grown for settings that aren't `0`.
+    pub fn write(&self, params: &mut Vec<i32>) {
+        write_param(params, Self::MODE, self.mode.into(), 0);
+        write_param(params, Self::USE_LENS, self.use_lens as i32, 0);
+    }
+
+    /// Serializing to JSON-string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// What an energy exchanger does with accumulators.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Copy, Clone, Default)]
+pub enum ExchangerMode {
+    #[default]
+    Standby,
+    /// Charges empty accumulators from the grid.
+    Charge,
+    /// Feeds the grid from full accumulators.
+    Discharge,
+    Unknown(i32),
+}
+
+impl From<i32> for ExchangerMode {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => ExchangerMode::Standby,
+            1 => ExchangerMode::Charge,
+            -1 => ExchangerMode::Discharge,
+            other => ExchangerMode::Unknown(other),
+        }
+    }
+}
+
+impl From<ExchangerMode> for i32 {
+    fn from(value: ExchangerMode) -> Self {
+        match value {
+            ExchangerMode::Standby => 0,
+            ExchangerMode::Charge => 1,
+            ExchangerMode::Discharge => -1,
+            ExchangerMode::Unknown(other) => other,
+        }
+    }
+}
+
+/// Settings of an energy exchanger, the mode is the first parameter, `0` if it's missing.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct ExchangerParameters {
+    pub mode: ExchangerMode,
+}
+
+impl ExchangerParameters {
+    const MODE: usize = 0;
+
+    pub fn new(params: &[i32]) -> Self {
+        ExchangerParameters {
+            mode: ExchangerMode::from(params.get(Self::MODE).copied().unwrap_or(0)),
+        }
+    }
+
+    /// Writes the mode into `params`, which is only grown for a mode other than standby.
+    pub fn write(&self, params: &mut Vec<i32>) {
+        write_param(params, Self::MODE, self.mode.into(), 0);
+    }
+
+    /// Serializing to JSON-string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// Settings of a fuel burning generator: thermal and mini fusion power stations and the
+/// artificial star.
+///
+/// The first parameter is the fuel the generator is limited to, `0` if it's missing.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct GeneratorParameters {
+    /// Only fuel the generator burns, `0` if it takes any fuel.
+    pub fuel_id: i32,
+}
+
+impl GeneratorParameters {
+    const FUEL_ID: usize = 0;
+
+    /// Whether `item` is a fuel burning generator.
+    pub fn is_generator(item: DysonSphereItem) -> bool {
+        matches!(
+            item,
+            DysonSphereItem::ThermalPowerStation
+                | DysonSphereItem::MiniFusionPowerStation
+                | DysonSphereItem::ArtificialStar
+        )
+    }
+
+    pub fn new(params: &[i32]) -> Self {
+        GeneratorParameters {
+            fuel_id: params.get(Self::FUEL_ID).copied().unwrap_or(0),
+        }
+    }
+
+    /// Fuel the generator is limited to, `None` if there's no limit or the item is unknown.
+    pub fn fuel(&self) -> Option<DysonSphereItem> {
+        DysonSphereItem::try_from(self.fuel_id).ok()
+    }
+
+    /// Writes the fuel into `params`, which is only grown for a set fuel.
+    pub fn write(&self, params: &mut Vec<i32>) {
+        write_param(params, Self::FUEL_ID, self.fuel_id, 0);
+    }
+
+    /// Serializing to JSON-string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ray_receiver() {
+        let receiver = RayReceiverParameters::new(&[1208, 1]);
+        assert_eq!(receiver.mode, RayReceiverMode::CriticalPhotons);
+        assert!(receiver.use_lens);
+        assert_eq!(
+            RayReceiverParameters::new(&[5]).mode,
+            RayReceiverMode::Unknown(5)
+        );
+
+        let mut params = Vec::new();
+        RayReceiverParameters::default().write(&mut params);
+        assert!(params.is_empty());
+        receiver.write(&mut params);
+        assert_eq!(params, [1208, 1]);
+    }
+
+    #[test]
+    fn test_exchanger_and_generator() {
+        let exchanger = ExchangerParameters::new(&[-1]);
+        assert_eq!(exchanger.mode, ExchangerMode::Discharge);
+        let mut params = vec![0, 9];
+        exchanger.write(&mut params);
+        assert_eq!(params, [-1, 9]);
+
+        let generator = GeneratorParameters::new(&[1803]);
+        assert_eq!(generator.fuel(), Some(DysonSphereItem::AntimatterFuelRod));
+        assert_eq!(GeneratorParameters::new(&[]).fuel(), None);
+        let mut params = Vec::new();
+        generator.write(&mut params);
+        assert_eq!(params, [1803]);
+    }
+}
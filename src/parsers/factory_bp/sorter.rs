@@ -0,0 +1,126 @@
+/*
+ * Copyright (c) 2025. sakost aka Konstantin Sazhenov
+ * All rights reserved.
+ */
+
+use crate::entities::item::DysonSphereItem;
+use crate::parsers::factory_bp::building::BlueprintBuilding;
+use crate::parsers::factory_bp::utils::write_param;
+use serde::{Deserialize, Serialize};
+
+/// Settings of a sorter, kept in `parameters` and `filter_id`.
+///
+/// The first parameter is the number of grid cells the sorter spans, the second the number of
+/// items it stacks before dropping them. Missing parameters are taken as `0`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct SorterParameters {
+    /// Grid cells between the two ends.
+    pub length: i32,
+    /// Items stacked on the output belt, `0` to not stack.
+    pub stack_count: i32,
+    /// Only item the sorter moves, `0` if there's no filter.
+    pub filter_id: i16,
+}
+
+impl SorterParameters {
+    const LENGTH: usize = 0;
+    const STACK_COUNT: usize = 1;
+
+    pub fn new(params: &[i32], filter_id: i16) -> Self {
+        let param = |i: usize| params.get(i).copied().unwrap_or(0);
+        SorterParameters {
+            length: param(Self::LENGTH),
+            stack_count: param(Self::STACK_COUNT),
+            filter_id,
+        }
+    }
+
+    /// Filter item, `None` if there's no filter or the item is unknown.
+    pub fn filter(&self) -> Option<DysonSphereItem> {
+        DysonSphereItem::try_from(self.filter_id as i32).ok()
+    }
+
+    /// Writes the settings into `params`, the filter goes to `filter_id` of the building.
+    ///
+    /// `params` is only grown for settings that aren't `0`.
+    pub fn write(&self, params: &mut Vec<i32>) {
+        write_param(params, Self::LENGTH, self.length, 0);
+        write_param(params, Self::STACK_COUNT, self.stack_count, 0);
+    }
+
+    /// Serializing to JSON-string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// One end of a sorter.
+#[derive(Debug, Serialize, PartialEq, Eq, Clone, Copy)]
+pub struct SorterEnd {
+    /// `index` of the building the end is attached to, `None` if it's free.
+    pub object_index: Option<i32>,
+    /// Position of that building in the blueprint, `None` if it's free or missing.
+    pub building: Option<usize>,
+    pub slot: i8,
+    pub offset: i8,
+}
+
+impl SorterEnd {
+    /// Whether the end is attached to a building which isn't in the blueprint.
+    pub fn is_dangling(&self) -> bool {
+        self.object_index.is_some() && self.building.is_none()
+    }
+}
+
+/// Where a sorter takes items from and where it drops them.
+#[derive(Debug, Serialize, PartialEq, Eq, Clone, Copy)]
+pub struct SorterConnection {
+    pub pickup: SorterEnd,
+    pub drop: SorterEnd,
+}
+
+impl SorterConnection {
+    /// Connection of `sorter`, `position` finds buildings by their `index`.
+    pub fn resolve(sorter: &BlueprintBuilding, position: impl Fn(i32) -> Option<usize>) -> Self {
+        let end = |object_index: i32, slot: i8, offset: i8| {
+            let object_index = (object_index >= 0).then_some(object_index);
+            SorterEnd {
+                object_index,
+                building: object_index.and_then(&position),
+                slot,
+                offset,
+            }
+        };
+        SorterConnection {
+            pickup: end(
+                sorter.input_object_index,
+                sorter.input_from_slot,
+                sorter.input_offset,
+            ),
+            drop: end(
+                sorter.output_object_index,
+                sorter.output_to_slot,
+                sorter.output_offset,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sorter() {
+        let sorter = SorterParameters::new(&[2, 4], DysonSphereItem::Gear as i16);
+        assert_eq!(sorter.length, 2);
+        assert_eq!(sorter.stack_count, 4);
+        assert_eq!(sorter.filter(), Some(DysonSphereItem::Gear));
+
+        let mut params = Vec::new();
+        SorterParameters::new(&[], 0).write(&mut params);
+        assert!(params.is_empty());
+        sorter.write(&mut params);
+        assert_eq!(params, [2, 4]);
+    }
+}
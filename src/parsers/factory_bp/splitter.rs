@@ -3,6 +3,121 @@
  * All rights reserved.
  */
 
-struct SplitterBuilding {
-    // todo: create splitter building
+use crate::entities::item::DysonSphereItem;
+use crate::parsers::factory_bp::utils::write_param;
+use serde::{Deserialize, Serialize};
+
+/// Priority settings of one side of a splitter.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Copy, Clone, Default)]
+pub struct SplitterSide {
+    pub input_priority: bool,
+    pub output_priority: bool,
+}
+
+impl SplitterSide {
+    const INPUT_PRIORITY: i32 = 1;
+    const OUTPUT_PRIORITY: i32 = 2;
+
+    fn from_int(value: i32) -> Self {
+        SplitterSide {
+            input_priority: value & Self::INPUT_PRIORITY != 0,
+            output_priority: value & Self::OUTPUT_PRIORITY != 0,
+        }
+    }
+
+    fn flags(&self) -> i32 {
+        (self.input_priority as i32 * Self::INPUT_PRIORITY)
+            | (self.output_priority as i32 * Self::OUTPUT_PRIORITY)
+    }
+}
+
+/// Splitter settings, kept in `parameters` and `filter_id` of the building.
+///
+/// The first four parameters hold the priority flags of the sides, the fifth whether the
+/// splitter has a box on top. Splitters without any settings may have no parameters at all.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct SplitterParameters {
+    pub sides: [SplitterSide; 4],
+    /// Item let through the output priority side, `0` if there's no filter.
+    pub filter_id: i16,
+    pub box_top: bool,
+}
+
+impl SplitterParameters {
+    const BOX_TOP: usize = 4;
+
+    /// Decodes the settings, missing parameters are taken as `0`.
+    pub fn new(params: &[i32], filter_id: i16) -> Self {
+        let param = |i: usize| params.get(i).copied().unwrap_or(0);
+        SplitterParameters {
+            sides: std::array::from_fn(|i| SplitterSide::from_int(param(i))),
+            filter_id,
+            box_top: param(Self::BOX_TOP) == 1,
+        }
+    }
+
+    /// Filter item, `None` if there's no filter or the item is unknown.
+    pub fn filter(&self) -> Option<DysonSphereItem> {
+        DysonSphereItem::try_from(self.filter_id as i32).ok()
+    }
+
+    /// Writes the settings into `params`, the filter goes to `filter_id` of the building.
+    ///
+    /// The array is only grown when a setting doesn't fit into it, and bits of the side ints
+    /// other than the priority flags are kept.
+    pub fn write(&self, params: &mut Vec<i32>) {
+        for (i, side) in self.sides.iter().enumerate() {
+            let other_bits = params.get(i).copied().unwrap_or(0)
+                & !(SplitterSide::INPUT_PRIORITY | SplitterSide::OUTPUT_PRIORITY);
+            write_param(params, i, other_bits | side.flags(), 0);
+        }
+        write_param(params, Self::BOX_TOP, self.box_top as i32, 0);
+    }
+
+    /// Serializing to JSON-string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let splitter = SplitterParameters::new(&[0, 1, 2, 7, 1], 1101);
+        assert_eq!(splitter.sides[0], SplitterSide::default());
+        assert!(splitter.sides[1].input_priority && !splitter.sides[1].output_priority);
+        assert!(!splitter.sides[2].input_priority && splitter.sides[2].output_priority);
+        assert!(splitter.sides[3].input_priority && splitter.sides[3].output_priority);
+        assert!(splitter.box_top);
+        assert_eq!(splitter.filter(), Some(DysonSphereItem::IronIngot));
+
+        let empty = SplitterParameters::new(&[], 0);
+        assert_eq!(empty, SplitterParameters::default());
+        assert_eq!(empty.filter(), None);
+    }
+
+    #[test]
+    fn test_write() {
+        let params = vec![0, 1, 2, 7, 1, 42];
+        let mut splitter = SplitterParameters::new(&params, 0);
+        let mut written = params.clone();
+        splitter.write(&mut written);
+        assert_eq!(written, params);
+
+        splitter.sides[3] = SplitterSide::default();
+        splitter.write(&mut written);
+        assert_eq!(written, [0, 1, 2, 4, 1, 42]);
+
+        let mut empty = Vec::new();
+        SplitterParameters::default().write(&mut empty);
+        assert!(empty.is_empty());
+
+        splitter.sides[1].output_priority = true;
+        splitter.box_top = false;
+        splitter.write(&mut empty);
+        assert_eq!(empty, [0, 3, 2]);
+    }
 }
@@ -6,33 +6,37 @@
 #![allow(unexpected_cfgs)]
 
 use crate::entities::item::DysonSphereItem;
-use crate::errors::UnknownDysonSphereItem;
-use serde::Serialize;
+use crate::errors::BlueprintError;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, PartialEq, Copy, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Copy, Clone)]
 pub struct StorageEntry {
     pub item_id: i32,
-    pub local_logic: i32,
-    pub remote_logic: i32,
+    pub local_logic: StorageLogic,
+    pub remote_logic: StorageLogic,
     pub max_count: i32,
-    pub keep_mode: i32,
+    pub keep_mode: StorageKeepMode,
+    /// `1` if proliferator points of the stored items are kept, other values are kept as they are.
     pub keep_inc: i32,
 }
 
 impl StorageEntry {
-    pub fn item(&self) -> Result<DysonSphereItem, UnknownDysonSphereItem> {
+    pub fn item(&self) -> Result<DysonSphereItem, BlueprintError> {
         DysonSphereItem::try_from(self.item_id)
     }
 }
 
-#[derive(Debug, Serialize, PartialEq, Copy, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Copy, Clone)]
 pub struct SlotEntry {
     pub direction: LogisticsStationDirection,
     pub storage_index: i32,
+    /// Last two ints of the slot, unused by the game so far.
+    pub reserved: [i32; 2],
 }
 
-#[derive(Debug, Serialize, PartialEq)]
-pub struct Parameters {
+/// Settings of the station as a whole, stored after the slots.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct StationSettings {
     pub work_energy: i32,
     pub drone_range: i32,
     pub vessel_range: i32,
@@ -41,31 +45,70 @@ pub struct Parameters {
     pub equip_warper: bool,
     pub drone_count: i32,
     pub vessel_count: i32,
+    /// Number of items stacked on the output belts.
     pub piler_count: i32,
+    /// Mining speed of an advanced mining machine in percent.
+    pub mining_speed: i32,
     pub drone_auto_replenish: bool,
     pub vessel_auto_replenish: bool,
+    /// Priority groups of the station, bit `n` is set for group `n + 1`.
+    pub priority_groups: u64,
+    pub route_priority: RoutePriority,
 }
 
-#[derive(Debug, Serialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct StationParameters {
     pub storage: Vec<Option<StorageEntry>>,
     pub slots: Vec<Option<SlotEntry>>,
-    pub parameters: Parameters,
+    pub parameters: StationSettings,
 }
 
 impl StationParameters {
     const STORAGE_OFFSET: usize = 0;
     const SLOTS_OFFSET: usize = Self::STORAGE_OFFSET + 192;
     const PARAMETERS_OFFSET: usize = Self::SLOTS_OFFSET + 128;
+    const PARAMETERS_LEN: usize = 15;
 
-    pub fn new(params: &[i32], storage_len: usize, slots_len: usize) -> Self {
+    /// Decodes station parameters, failing if `params` is too short for the layout.
+    pub fn new(
+        params: &[i32],
+        storage_len: usize,
+        slots_len: usize,
+    ) -> Result<Self, BlueprintError> {
+        if params.len() < Self::PARAMETERS_OFFSET + Self::PARAMETERS_LEN {
+            return Err(Self::layout_error(params.len(), storage_len, slots_len));
+        }
+        Self::check_layout(params.len(), storage_len, slots_len)?;
         let storage = Self::parse_storage(params, storage_len);
         let slots = Self::parse_slots(params, slots_len);
         let parameters = Self::parse_parameters(params);
-        StationParameters {
+        Ok(StationParameters {
             storage,
             slots,
             parameters,
+        })
+    }
+
+    /// Checks that `storage_len` storage and `slots_len` slot entries fit their blocks.
+    fn check_layout(
+        params_len: usize,
+        storage_len: usize,
+        slots_len: usize,
+    ) -> Result<(), BlueprintError> {
+        if storage_len * 6 > Self::SLOTS_OFFSET - Self::STORAGE_OFFSET
+            || slots_len * 4 > Self::PARAMETERS_OFFSET - Self::SLOTS_OFFSET
+        {
+            return Err(Self::layout_error(params_len, storage_len, slots_len));
+        }
+        Ok(())
+    }
+
+    fn layout_error(params_len: usize, storage_len: usize, slots_len: usize) -> BlueprintError {
+        BlueprintError::StationLayout {
+            building: None,
+            params_len,
+            storage_len,
+            slots_len,
         }
     }
 
@@ -79,10 +122,10 @@ impl StationParameters {
             } else {
                 storage.push(Some(StorageEntry {
                     item_id,
-                    local_logic: params[offset + 1],
-                    remote_logic: params[offset + 2],
+                    local_logic: StorageLogic::from(params[offset + 1]),
+                    remote_logic: StorageLogic::from(params[offset + 2]),
                     max_count: params[offset + 3],
-                    keep_mode: params[offset + 4],
+                    keep_mode: StorageKeepMode::from(params[offset + 4]),
                     keep_inc: params[offset + 5],
                 }));
             }
@@ -99,8 +142,9 @@ impl StationParameters {
                 slots.push(None);
             } else {
                 slots.push(Some(SlotEntry {
-                    direction: LogisticsStationDirection::from(params[offset] as u8),
+                    direction: LogisticsStationDirection::from(params[offset]),
                     storage_index,
+                    reserved: [params[offset + 2], params[offset + 3]],
                 }));
             }
         }
@@ -108,8 +152,8 @@ impl StationParameters {
     }
 
     #[allow(clippy::identity_op)]
-    fn parse_parameters(params: &[i32]) -> Parameters {
-        Parameters {
+    fn parse_parameters(params: &[i32]) -> StationSettings {
+        StationSettings {
             work_energy: params[Self::PARAMETERS_OFFSET + 0],
             drone_range: params[Self::PARAMETERS_OFFSET + 1],
             vessel_range: params[Self::PARAMETERS_OFFSET + 2],
@@ -119,14 +163,131 @@ impl StationParameters {
             drone_count: params[Self::PARAMETERS_OFFSET + 6],
             vessel_count: params[Self::PARAMETERS_OFFSET + 7],
             piler_count: params[Self::PARAMETERS_OFFSET + 8],
-            // 9 is for vein collector speed mining
+            mining_speed: params[Self::PARAMETERS_OFFSET + 9],
             drone_auto_replenish: params[Self::PARAMETERS_OFFSET + 10] == 1,
             vessel_auto_replenish: params[Self::PARAMETERS_OFFSET + 11] == 1,
-            // 12, 13 for group priority
-            // 14 is for route priority
+            priority_groups: params[Self::PARAMETERS_OFFSET + 12] as u32 as u64
+                | (params[Self::PARAMETERS_OFFSET + 13] as u32 as u64) << 32,
+            route_priority: RoutePriority::from(params[Self::PARAMETERS_OFFSET + 14]),
         }
     }
 
+    /// Writes the station into `params`, growing it to the station layout if needed.
+    ///
+    /// Ints the station doesn't model are left as they are, empty storage and slot entries only
+    /// clear the item and the storage index. Fails if there are more storage or slot entries
+    /// than the layout has room for.
+    pub fn write(&self, params: &mut Vec<i32>) -> Result<(), BlueprintError> {
+        Self::check_layout(params.len(), self.storage.len(), self.slots.len())?;
+        let min_len = Self::PARAMETERS_OFFSET + Self::PARAMETERS_LEN;
+        if params.len() < min_len {
+            params.resize(min_len, 0);
+        }
+        for (i, entry) in self.storage.iter().enumerate() {
+            let offset = Self::STORAGE_OFFSET + i * 6;
+            match entry {
+                None => params[offset] = 0,
+                Some(entry) => params[offset..offset + 6].copy_from_slice(&[
+                    entry.item_id,
+                    entry.local_logic.into(),
+                    entry.remote_logic.into(),
+                    entry.max_count,
+                    entry.keep_mode.into(),
+                    entry.keep_inc,
+                ]),
+            }
+        }
+        for (i, entry) in self.slots.iter().enumerate() {
+            let offset = Self::SLOTS_OFFSET + i * 4;
+            match entry {
+                None => params[offset + 1] = 0,
+                Some(entry) => {
+                    params[offset] = entry.direction.into();
+                    params[offset + 1] = entry.storage_index;
+                    params[offset + 2..offset + 4].copy_from_slice(&entry.reserved);
+                }
+            }
+        }
+        self.write_parameters(params);
+        Ok(())
+    }
+
+    #[allow(clippy::identity_op)]
+    fn write_parameters(&self, params: &mut [i32]) {
+        let parameters = &self.parameters;
+        params[Self::PARAMETERS_OFFSET + 0] = parameters.work_energy;
+        params[Self::PARAMETERS_OFFSET + 1] = parameters.drone_range;
+        params[Self::PARAMETERS_OFFSET + 2] = parameters.vessel_range;
+        params[Self::PARAMETERS_OFFSET + 3] = parameters.orbital_collector as i32;
+        params[Self::PARAMETERS_OFFSET + 4] = parameters.warp_distance;
+        params[Self::PARAMETERS_OFFSET + 5] = parameters.equip_warper as i32;
+        params[Self::PARAMETERS_OFFSET + 6] = parameters.drone_count;
+        params[Self::PARAMETERS_OFFSET + 7] = parameters.vessel_count;
+        params[Self::PARAMETERS_OFFSET + 8] = parameters.piler_count;
+        params[Self::PARAMETERS_OFFSET + 9] = parameters.mining_speed;
+        params[Self::PARAMETERS_OFFSET + 10] = parameters.drone_auto_replenish as i32;
+        params[Self::PARAMETERS_OFFSET + 11] = parameters.vessel_auto_replenish as i32;
+        params[Self::PARAMETERS_OFFSET + 12] = parameters.priority_groups as u32 as i32;
+        params[Self::PARAMETERS_OFFSET + 13] = (parameters.priority_groups >> 32) as u32 as i32;
+        params[Self::PARAMETERS_OFFSET + 14] = parameters.route_priority.into();
+    }
+
+    /// Kind of the station, by the number of storage entries.
+    pub fn kind(&self) -> Option<StationKind> {
+        match self.storage.len() {
+            1 => Some(StationKind::AdvancedMiningMachine),
+            2 => Some(StationKind::OrbitalCollector),
+            4 => Some(StationKind::Planetary),
+            5 => Some(StationKind::Interstellar),
+            _ => None,
+        }
+    }
+
+    /// Turns a logistics station into a station of `kind`, returns `false` if it's already of
+    /// `kind` or either of them isn't a logistics station.
+    ///
+    /// An interstellar station gets an empty fifth storage and the default vessel range, warp
+    /// distance and warper setting. A planetary one loses the fifth storage together with the
+    /// slots bound to it, and everything about vessels and warpers.
+    pub fn convert(&mut self, kind: StationKind) -> bool {
+        let current = self.kind();
+        if current == Some(kind)
+            || !kind.is_logistics()
+            || !current.is_some_and(|current| current.is_logistics())
+        {
+            return false;
+        }
+        self.storage.resize(kind.storage_len(), None);
+        let parameters = &mut self.parameters;
+        match kind {
+            StationKind::Interstellar => {
+                parameters.vessel_range = StationKind::DEFAULT_VESSEL_RANGE;
+                parameters.warp_distance = StationKind::DEFAULT_WARP_DISTANCE;
+                parameters.equip_warper = true;
+            }
+            StationKind::Planetary => {
+                for slot in self.slots.iter_mut() {
+                    if slot.is_some_and(|slot| slot.storage_index > 4) {
+                        *slot = None;
+                    }
+                }
+                for entry in self.storage.iter_mut().flatten() {
+                    entry.remote_logic = StorageLogic::Storage;
+                }
+                parameters.work_energy = parameters
+                    .work_energy
+                    .min(StationKind::PLANETARY_MAX_WORK_ENERGY);
+                parameters.vessel_count = 0;
+                parameters.vessel_auto_replenish = false;
+                parameters.equip_warper = false;
+                parameters.priority_groups = 0;
+                parameters.route_priority = RoutePriority::Ignore;
+            }
+            StationKind::AdvancedMiningMachine | StationKind::OrbitalCollector => unreachable!(),
+        }
+        true
+    }
+
     pub fn is_interstellar(&self) -> bool {
         self.storage.len() == 5
     }
@@ -140,17 +301,99 @@ impl StationParameters {
     }
 }
 
-#[derive(Debug, Serialize, PartialEq, Copy, Clone)]
+/// Building using the station parameter layout.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Copy, Clone)]
+pub enum StationKind {
+    Planetary,
+    Interstellar,
+    /// Mines veins into its only storage.
+    AdvancedMiningMachine,
+    /// Collects gas of a gas giant into its two storages, has no belt slots.
+    OrbitalCollector,
+}
+
+impl StationKind {
+    /// Vessel range of a new interstellar station, 10 light years.
+    pub const DEFAULT_VESSEL_RANGE: i32 = 240_000;
+    /// Warp distance of a new interstellar station, 0.5 AU.
+    pub const DEFAULT_WARP_DISTANCE: i32 = 20_000;
+    /// Charging power of a planetary station per tick, 60 MW.
+    pub const PLANETARY_MAX_WORK_ENERGY: i32 = 1_000_000;
+
+    /// Kind of the station built from `item`.
+    pub fn of(item: DysonSphereItem) -> Option<Self> {
+        match item {
+            DysonSphereItem::PlanetaryLogisticsStation => Some(StationKind::Planetary),
+            DysonSphereItem::InterstellarLogisticsStation => Some(StationKind::Interstellar),
+            DysonSphereItem::AdvancedMiningMachine => Some(StationKind::AdvancedMiningMachine),
+            DysonSphereItem::OrbitalCollector => Some(StationKind::OrbitalCollector),
+            _ => None,
+        }
+    }
+
+    pub fn item(&self) -> DysonSphereItem {
+        match self {
+            StationKind::Planetary => DysonSphereItem::PlanetaryLogisticsStation,
+            StationKind::Interstellar => DysonSphereItem::InterstellarLogisticsStation,
+            StationKind::AdvancedMiningMachine => DysonSphereItem::AdvancedMiningMachine,
+            StationKind::OrbitalCollector => DysonSphereItem::OrbitalCollector,
+        }
+    }
+
+    /// Model of a logistics station, `None` for the other kinds.
+    pub fn model_index(&self) -> Option<i16> {
+        match self {
+            StationKind::Planetary => Some(49),
+            StationKind::Interstellar => Some(50),
+            StationKind::AdvancedMiningMachine | StationKind::OrbitalCollector => None,
+        }
+    }
+
+    pub fn storage_len(&self) -> usize {
+        match self {
+            StationKind::AdvancedMiningMachine => 1,
+            StationKind::OrbitalCollector => 2,
+            StationKind::Planetary => 4,
+            StationKind::Interstellar => 5,
+        }
+    }
+
+    pub fn slots_len(&self) -> usize {
+        match self {
+            StationKind::OrbitalCollector => 0,
+            _ => 12,
+        }
+    }
+
+    /// Whether it's a planetary or an interstellar logistics station.
+    pub fn is_logistics(&self) -> bool {
+        matches!(self, StationKind::Planetary | StationKind::Interstellar)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Copy, Clone)]
 pub enum LogisticsStationDirection {
     North,
     East,
     South,
     West,
-    Unknown(u8),
+    Unknown(i32),
+}
+
+impl From<LogisticsStationDirection> for i32 {
+    fn from(value: LogisticsStationDirection) -> Self {
+        match value {
+            LogisticsStationDirection::North => 0,
+            LogisticsStationDirection::East => 1,
+            LogisticsStationDirection::South => 2,
+            LogisticsStationDirection::West => 3,
+            LogisticsStationDirection::Unknown(other) => other,
+        }
+    }
 }
 
-impl From<u8> for LogisticsStationDirection {
-    fn from(value: u8) -> Self {
+impl From<i32> for LogisticsStationDirection {
+    fn from(value: i32) -> Self {
         match value {
             0 => LogisticsStationDirection::North,
             1 => LogisticsStationDirection::East,
@@ -161,9 +404,307 @@ impl From<u8> for LogisticsStationDirection {
     }
 }
 
+/// What the station does with an item, for local and remote transport separately.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Copy, Clone)]
+pub enum StorageLogic {
+    Storage,
+    Supply,
+    Demand,
+    Unknown(i32),
+}
+
+impl From<i32> for StorageLogic {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => StorageLogic::Storage,
+            1 => StorageLogic::Supply,
+            2 => StorageLogic::Demand,
+            other => StorageLogic::Unknown(other),
+        }
+    }
+}
+
+impl From<StorageLogic> for i32 {
+    fn from(value: StorageLogic) -> Self {
+        match value {
+            StorageLogic::Storage => 0,
+            StorageLogic::Supply => 1,
+            StorageLogic::Demand => 2,
+            StorageLogic::Unknown(other) => other,
+        }
+    }
+}
+
+/// How many items the station keeps instead of handing them out.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Copy, Clone)]
+pub enum StorageKeepMode {
+    None,
+    /// Keeps items for local transport.
+    Local,
+    /// Keeps items for remote transport.
+    Remote,
+    /// Keeps all items.
+    All,
+    Unknown(i32),
+}
+
+impl From<i32> for StorageKeepMode {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => StorageKeepMode::None,
+            1 => StorageKeepMode::Local,
+            2 => StorageKeepMode::Remote,
+            3 => StorageKeepMode::All,
+            other => StorageKeepMode::Unknown(other),
+        }
+    }
+}
+
+impl From<StorageKeepMode> for i32 {
+    fn from(value: StorageKeepMode) -> Self {
+        match value {
+            StorageKeepMode::None => 0,
+            StorageKeepMode::Local => 1,
+            StorageKeepMode::Remote => 2,
+            StorageKeepMode::All => 3,
+            StorageKeepMode::Unknown(other) => other,
+        }
+    }
+}
+
+/// How an interstellar station treats its priority groups when choosing routes.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Copy, Clone)]
+pub enum RoutePriority {
+    /// Groups are ignored.
+    Ignore,
+    /// Stations of the same group are preferred.
+    Prioritize,
+    /// Only stations of the same group are served.
+    Only,
+    /// Only designated routes are served.
+    Designated,
+    Unknown(i32),
+}
+
+impl From<i32> for RoutePriority {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => RoutePriority::Ignore,
+            1 => RoutePriority::Prioritize,
+            2 => RoutePriority::Only,
+            3 => RoutePriority::Designated,
+            other => RoutePriority::Unknown(other),
+        }
+    }
+}
+
+impl From<RoutePriority> for i32 {
+    fn from(value: RoutePriority) -> Self {
+        match value {
+            RoutePriority::Ignore => 0,
+            RoutePriority::Prioritize => 1,
+            RoutePriority::Only => 2,
+            RoutePriority::Designated => 3,
+            RoutePriority::Unknown(other) => other,
+        }
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
-    use crate::parsers::factory_bp::station::LogisticsStationDirection;
+    use crate::entities::item::DysonSphereItem;
+    use crate::parsers::factory_bp::station::{
+        LogisticsStationDirection, RoutePriority, StationKind, StationParameters, StorageEntry,
+        StorageKeepMode, StorageLogic,
+    };
+
+    #[test]
+    fn test_short_parameters() {
+        assert!(StationParameters::new(&[], 5, 12).is_err());
+        assert!(StationParameters::new(&[0; 334], 5, 12).is_err());
+        assert!(StationParameters::new(&[0; 335], 5, 12).is_ok());
+        assert!(StationParameters::new(&[0; 335], 33, 12).is_err());
+        assert!(StationParameters::new(&[0; 335], 5, 33).is_err());
+    }
+
+    #[test]
+    fn test_write_round_trip() {
+        let mut params: Vec<i32> = (0..400).map(|i| i % 7).collect();
+        params[0] = 1001; // first storage item
+        params[6] = 0; // second storage is empty
+        params[192 + 1] = 1; // first slot uses storage 1
+        params[192 + 4 + 1] = 0; // second slot is empty
+        for (flag, value) in [(3, 1), (5, 0), (10, 1), (11, 0)] {
+            params[320 + flag] = value;
+        }
+        let station = StationParameters::new(&params, 5, 12).unwrap();
+
+        let mut written = params.clone();
+        station.write(&mut written).unwrap();
+        assert_eq!(written, params);
+
+        let mut short = Vec::new();
+        station.write(&mut short).unwrap();
+        assert_eq!(short.len(), 335);
+        assert_eq!(StationParameters::new(&short, 5, 12).unwrap(), station);
+
+        let mut too_large = station.clone();
+        too_large.storage.resize(33, None);
+        assert!(too_large.write(&mut written).is_err());
+        assert_eq!(written, params);
+    }
+
+    #[test]
+    fn test_write_changes_only_edited_ints() {
+        let mut params: Vec<i32> = (0..2048).map(|i| i % 5).collect();
+        // station flags only keep 0 and 1
+        params[320 + 3] = 0;
+        let mut station = StationParameters::new(&params, 5, 12).unwrap();
+        station.storage[4] = Some(StorageEntry {
+            item_id: DysonSphereItem::SpaceWarper as i32,
+            local_logic: StorageLogic::Supply,
+            remote_logic: StorageLogic::Demand,
+            max_count: 1000,
+            keep_mode: StorageKeepMode::None,
+            keep_inc: 0,
+        });
+        station.slots[11] = None;
+        station.slots[0].as_mut().unwrap().direction = LogisticsStationDirection::West;
+        station.parameters.drone_count = 100;
+        station.parameters.vessel_count = 10;
+        station.parameters.equip_warper = true;
+        station.parameters.warp_distance = 60000;
+
+        let mut written = params.clone();
+        station.write(&mut written).unwrap();
+        assert_eq!(StationParameters::new(&written, 5, 12).unwrap(), station);
+        let changed: Vec<usize> = (0..2048).filter(|&i| written[i] != params[i]).collect();
+        assert_eq!(
+            changed,
+            [24, 25, 26, 27, 28, 29, 192, 237, 324, 325, 326, 327]
+        );
+    }
+
+    #[test]
+    fn test_mining_machine_and_collector() {
+        let kind = StationKind::AdvancedMiningMachine;
+        let mut params = vec![0; 2048];
+        params[0..4].copy_from_slice(&[DysonSphereItem::IronOre as i32, 1, 0, 0]);
+        params[192..194].copy_from_slice(&[2, 1]);
+        params[320 + 8] = 4;
+        params[320 + 9] = 300;
+        let miner = StationParameters::new(&params, kind.storage_len(), kind.slots_len()).unwrap();
+        assert_eq!(miner.kind(), Some(kind));
+        assert_eq!(
+            miner.storage[0].unwrap().item().unwrap(),
+            DysonSphereItem::IronOre
+        );
+        assert_eq!(miner.slots.len(), 12);
+        assert!(miner.slots[0].is_some());
+        assert_eq!(miner.parameters.piler_count, 4);
+        assert_eq!(miner.parameters.mining_speed, 300);
+
+        let kind = StationKind::OrbitalCollector;
+        let mut params = vec![0; 2048];
+        params[0] = DysonSphereItem::Hydrogen as i32;
+        params[6] = DysonSphereItem::Deuterium as i32;
+        let collector =
+            StationParameters::new(&params, kind.storage_len(), kind.slots_len()).unwrap();
+        assert_eq!(collector.kind(), Some(kind));
+        assert_eq!(collector.storage.len(), 2);
+        assert!(collector.slots.is_empty());
+    }
+
+    #[test]
+    fn test_remaining_fields() {
+        let mut params = vec![0; 2048];
+        params[0] = 1001;
+        params[1] = 1;
+        params[2] = 2;
+        params[3] = 5000;
+        params[4] = 3;
+        params[5] = 1;
+        params[6] = 1002;
+        params[7] = 7;
+        params[10] = 9;
+        params[11] = 3;
+        params[192..196].copy_from_slice(&[2, 1, 4, 5]);
+        params[320 + 9] = 300;
+        params[320 + 12] = -1;
+        params[320 + 13] = 1;
+        params[320 + 14] = 2;
+        let station = StationParameters::new(&params, 5, 12).unwrap();
+
+        let first = station.storage[0].unwrap();
+        assert_eq!(first.local_logic, StorageLogic::Supply);
+        assert_eq!(first.remote_logic, StorageLogic::Demand);
+        assert_eq!(first.max_count, 5000);
+        assert_eq!(first.keep_mode, StorageKeepMode::All);
+        assert_eq!(first.keep_inc, 1);
+        let second = station.storage[1].unwrap();
+        assert_eq!(second.local_logic, StorageLogic::Unknown(7));
+        assert_eq!(second.keep_mode, StorageKeepMode::Unknown(9));
+        assert_eq!(second.keep_inc, 3);
+
+        let slot = station.slots[0].unwrap();
+        assert_eq!(slot.direction, LogisticsStationDirection::South);
+        assert_eq!(slot.reserved, [4, 5]);
+
+        assert_eq!(station.parameters.mining_speed, 300);
+        assert_eq!(station.parameters.priority_groups, 0x1_FFFF_FFFF);
+        assert_eq!(station.parameters.route_priority, RoutePriority::Only);
+
+        let mut written = params.clone();
+        station.write(&mut written).unwrap();
+        assert_eq!(written, params);
+    }
+
+    #[test]
+    fn test_convert() {
+        let mut params = vec![0; 2048];
+        for i in 0..5 {
+            params[i * 6..i * 6 + 4].copy_from_slice(&[1001 + i as i32, 1, 2, 100]);
+        }
+        params[192..196].copy_from_slice(&[0, 1, 0, 0]);
+        params[196..200].copy_from_slice(&[0, 5, 0, 0]);
+        params[320] = 5_000_000;
+        params[320 + 7] = 10;
+        params[320 + 14] = 1;
+        let interstellar = StationParameters::new(&params, 5, 12).unwrap();
+
+        let mut planetary = interstellar.clone();
+        assert!(planetary.convert(StationKind::Planetary));
+        assert!(!planetary.convert(StationKind::Planetary));
+        assert!(!planetary.convert(StationKind::OrbitalCollector));
+        assert_eq!(planetary.kind(), Some(StationKind::Planetary));
+        assert_eq!(planetary.storage, {
+            let mut storage = interstellar.storage[..4].to_vec();
+            for entry in storage.iter_mut().flatten() {
+                entry.remote_logic = StorageLogic::Storage;
+            }
+            storage
+        });
+        assert_eq!(planetary.slots[0], interstellar.slots[0]);
+        assert_eq!(planetary.slots[1], None);
+        assert_eq!(planetary.parameters.work_energy, 1_000_000);
+        assert_eq!(planetary.parameters.vessel_count, 0);
+        assert_eq!(planetary.parameters.route_priority, RoutePriority::Ignore);
+
+        let mut back = planetary.clone();
+        assert!(back.convert(StationKind::Interstellar));
+        assert_eq!(back.kind(), Some(StationKind::Interstellar));
+        assert_eq!(back.storage[..4], planetary.storage[..]);
+        assert_eq!(back.storage[4], None);
+        assert_eq!(
+            back.parameters.vessel_range,
+            StationKind::DEFAULT_VESSEL_RANGE
+        );
+        assert_eq!(
+            back.parameters.warp_distance,
+            StationKind::DEFAULT_WARP_DISTANCE
+        );
+        assert!(back.parameters.equip_warper);
+    }
 
     #[test]
     fn test_logistics_station_direction() {
@@ -187,6 +728,11 @@ pub mod tests {
             LogisticsStationDirection::from(10),
             LogisticsStationDirection::Unknown(10)
         );
+        for value in [257, -1] {
+            let direction = LogisticsStationDirection::from(value);
+            assert_eq!(direction, LogisticsStationDirection::Unknown(value));
+            assert_eq!(i32::from(direction), value);
+        }
     }
 }
 #[cfg(FALSE)]
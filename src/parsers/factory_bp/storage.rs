@@ -0,0 +1,172 @@
+/*
+ * Copyright (c) 2025. sakost aka Konstantin Sazhenov
+ * All rights reserved.
+ */
+
+use crate::entities::item::DysonSphereItem;
+use crate::parsers::factory_bp::utils::write_param;
+use serde::{Deserialize, Serialize};
+
+/// Settings of a storage box.
+///
+/// The first parameter is the number of slots behind the limit bar, the following ones hold
+/// the filter item of every slot. Missing parameters are taken as `0`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct StorageParameters {
+    /// Slots behind the limit bar, counted from the end, which sorters don't fill.
+    pub banned_slots: i32,
+    /// Filter item of every slot, `0` if the slot takes any item.
+    pub filters: Vec<i32>,
+}
+
+impl StorageParameters {
+    const BANNED_SLOTS: usize = 0;
+    const FILTERS_OFFSET: usize = 1;
+
+    /// Number of slots of a storage box made of `item`, `None` if it isn't a storage box.
+    pub fn slot_count(item: DysonSphereItem) -> Option<usize> {
+        match item {
+            DysonSphereItem::StorageMKI => Some(30),
+            DysonSphereItem::StorageMKII => Some(60),
+            _ => None,
+        }
+    }
+
+    /// Decodes the settings of a box with `slot_count` slots.
+    pub fn new(params: &[i32], slot_count: usize) -> Self {
+        let param = |i: usize| params.get(i).copied().unwrap_or(0);
+        StorageParameters {
+            banned_slots: param(Self::BANNED_SLOTS),
+            filters: (0..slot_count)
+                .map(|i| param(Self::FILTERS_OFFSET + i))
+                .collect(),
+        }
+    }
+
+    /// Number of slots in front of the limit bar.
+    pub fn usable_slots(&self) -> usize {
+        self.filters
+            .len()
+            .saturating_sub(self.banned_slots.max(0) as usize)
+    }
+
+    /// Filter item of slot `i`, `None` if there's no filter or the item is unknown.
+    pub fn filter(&self, i: usize) -> Option<DysonSphereItem> {
+        let id = *self.filters.get(i)?;
+        DysonSphereItem::try_from(id).ok()
+    }
+
+    /// Writes the settings into `params`, which is only grown for settings that aren't `0`.
+    pub fn write(&self, params: &mut Vec<i32>) {
+        write_param(params, Self::BANNED_SLOTS, self.banned_slots, 0);
+        for (i, filter) in self.filters.iter().enumerate() {
+            write_param(params, Self::FILTERS_OFFSET + i, *filter, 0);
+        }
+    }
+
+    /// Serializing to JSON-string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// Settings of a storage tank.
+///
+/// The parameters hold the input and the output switch. Tanks start with both switched on, so
+/// missing parameters are taken as on.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct TankParameters {
+    /// Whether the tank takes fluid from the tank below it.
+    pub input: bool,
+    /// Whether the tank passes fluid to the tank below it.
+    pub output: bool,
+}
+
+impl Default for TankParameters {
+    fn default() -> Self {
+        TankParameters {
+            input: true,
+            output: true,
+        }
+    }
+}
+
+impl TankParameters {
+    const INPUT: usize = 0;
+    const OUTPUT: usize = 1;
+
+    pub fn new(params: &[i32]) -> Self {
+        let switch = |i: usize| params.get(i).map_or(true, |value| *value == 1);
+        TankParameters {
+            input: switch(Self::INPUT),
+            output: switch(Self::OUTPUT),
+        }
+    }
+
+    /// Writes the switches into `params`, which is only grown for switches that are off.
+    pub fn write(&self, params: &mut Vec<i32>) {
+        write_param(params, Self::INPUT, self.input as i32, 1);
+        write_param(params, Self::OUTPUT, self.output as i32, 1);
+    }
+
+    /// Serializing to JSON-string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storage() {
+        let mut params = vec![10, 0, 1101, 0];
+        let storage = StorageParameters::new(&params, 30);
+        assert_eq!(storage.banned_slots, 10);
+        assert_eq!(storage.usable_slots(), 20);
+        assert_eq!(storage.filters.len(), 30);
+        assert_eq!(storage.filter(0), None);
+        assert_eq!(storage.filter(1), Some(DysonSphereItem::IronIngot));
+
+        let mut written = params.clone();
+        storage.write(&mut written);
+        assert_eq!(written, params);
+
+        let mut changed = storage.clone();
+        changed.banned_slots = 0;
+        changed.filters[4] = 1104;
+        changed.write(&mut params);
+        assert_eq!(params, [0, 0, 1101, 0, 0, 1104]);
+
+        let mut empty = Vec::new();
+        StorageParameters::new(&[], 60).write(&mut empty);
+        assert!(empty.is_empty());
+
+        assert_eq!(
+            StorageParameters::slot_count(DysonSphereItem::StorageMKII),
+            Some(60)
+        );
+        assert_eq!(
+            StorageParameters::slot_count(DysonSphereItem::StorageTank),
+            None
+        );
+    }
+
+    #[test]
+    fn test_tank() {
+        assert_eq!(TankParameters::new(&[]), TankParameters::default());
+        let tank = TankParameters::new(&[1, 0, 7]);
+        assert!(tank.input && !tank.output);
+
+        let mut params = vec![1, 0, 7];
+        tank.write(&mut params);
+        assert_eq!(params, [1, 0, 7]);
+
+        let mut empty = Vec::new();
+        TankParameters::default().write(&mut empty);
+        assert!(empty.is_empty());
+        tank.write(&mut empty);
+        assert_eq!(empty, [1, 0]);
+    }
+}
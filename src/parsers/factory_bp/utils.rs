@@ -3,50 +3,156 @@
  * All rights reserved.
  */
 
+use crate::errors::{BlueprintError, Location, Section};
+
+/// Bounds-checked little-endian reader over blueprint data.
+///
+/// Every read either returns the value and moves the cursor forward or fails with the location
+/// which couldn't be read, so malformed input never panics. The section and building being
+/// decoded are set by the deserializers and reported in errors.
+#[derive(Debug, Clone)]
+pub(crate) struct BinaryReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+    section: Section,
+    building: Option<usize>,
+}
+
 macro_rules! define_read_fn {
     ($func_name:ident, $type:ty, $size:expr) => {
         #[inline]
-        pub fn $func_name(data: &[u8], offset: usize) -> ($type, usize) {
-            let bytes: [u8; $size] = data[offset..offset + $size]
+        pub fn $func_name(&mut self) -> Result<$type, BlueprintError> {
+            let bytes: [u8; $size] = self
+                .read_bytes($size)?
                 .try_into()
-                .expect("slice with incorrect length");
-            (<$type>::from_le_bytes(bytes), offset + $size)
+                .expect("read_bytes returns exactly the requested length");
+            Ok(<$type>::from_le_bytes(bytes))
+        }
+    };
+}
+
+impl<'a> BinaryReader<'a> {
+    pub fn new(data: &'a [u8], offset: usize) -> Self {
+        BinaryReader {
+            data,
+            offset,
+            section: Section::DataHeader,
+            building: None,
+        }
+    }
+
+    /// Sets the section reported by errors from now on.
+    pub fn set_section(&mut self, section: Section) {
+        self.section = section;
+    }
+
+    /// Sets the building index reported by errors from now on.
+    pub fn set_building(&mut self, building: Option<usize>) {
+        self.building = building;
+    }
+
+    /// Location of the cursor.
+    pub fn location(&self) -> Location {
+        self.location_at(self.offset)
+    }
+
+    /// Location of `offset` within the current section and building.
+    pub fn location_at(&self, offset: usize) -> Location {
+        Location {
+            section: self.section,
+            offset,
+            building: self.building,
+        }
+    }
+
+    /// Offset of the next byte to be read.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Number of bytes left after the cursor.
+    pub fn remaining(&self) -> usize {
+        self.data.len().saturating_sub(self.offset)
+    }
+
+    /// Reads `len` raw bytes.
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], BlueprintError> {
+        self.ensure(len)?;
+        let bytes = &self.data[self.offset..self.offset + len];
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    /// Fails unless at least `len` bytes are left, without moving the cursor.
+    pub fn ensure(&self, len: usize) -> Result<(), BlueprintError> {
+        if self.remaining() < len {
+            return Err(BlueprintError::TruncatedData {
+                location: self.location(),
+                needed: len,
+                available: self.remaining(),
+            });
+        }
+        Ok(())
+    }
+
+    define_read_fn!(read_f32, f32, 4);
+    define_read_fn!(read_i8, i8, 1);
+    define_read_fn!(read_i16, i16, 2);
+    define_read_fn!(read_i32, i32, 4);
+}
+
+macro_rules! define_write_fn {
+    ($func_name:ident, $type:ty) => {
+        #[inline]
+        pub fn $func_name(data: &mut Vec<u8>, value: $type) {
+            data.extend_from_slice(&value.to_le_bytes());
         }
     };
 }
 
-define_read_fn!(read_u8, u8, 1);
-define_read_fn!(read_u16, u16, 2);
-define_read_fn!(read_u32, u32, 4);
-define_read_fn!(read_f32, f32, 4);
-define_read_fn!(read_i8, i8, 1);
-define_read_fn!(read_i16, i16, 2);
-define_read_fn!(read_i32, i32, 4);
+define_write_fn!(write_f32, f32);
+define_write_fn!(write_i8, i8);
+define_write_fn!(write_i16, i16);
+define_write_fn!(write_i32, i32);
+
+/// Sets `params[i]`, growing `params` with `missing`, the value missing ints are read as, only
+/// if `value` differs from it.
+pub(crate) fn write_param(params: &mut Vec<i32>, i: usize, value: i32, missing: i32) {
+    if i >= params.len() {
+        if value == missing {
+            return;
+        }
+        params.resize(i + 1, missing);
+    }
+    params[i] = value;
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_read_u8() {
+    fn test_read_i8() {
         let data = [10, 20, 30];
-        assert_eq!(read_u8(&data, 1), (20, 2));
+        let mut reader = BinaryReader::new(&data, 1);
+        assert_eq!(reader.read_i8().unwrap(), 20);
+        assert_eq!(reader.offset(), 2);
     }
 
     #[test]
-    fn test_read_u16() {
+    fn test_read_i16() {
         let data = [0x34, 0x12, 0x78, 0x56];
-        let offset = 0;
-        let (result, offset) = read_u16(&data, offset);
-        assert_eq!(result, 0x1234);
-        let (result, _) = read_u16(&data, offset);
-        assert_eq!(result, 0x5678);
+        let mut reader = BinaryReader::new(&data, 0);
+        assert_eq!(reader.read_i16().unwrap(), 0x1234);
+        assert_eq!(reader.read_i16().unwrap(), 0x5678);
     }
 
     #[test]
-    fn test_read_u32() {
+    fn test_read_i32() {
         let data = [0x78, 0x56, 0x34, 0x12];
-        assert_eq!(read_u32(&data, 0), (0x12345678, 4));
+        let mut reader = BinaryReader::new(&data, 0);
+        assert_eq!(reader.read_i32().unwrap(), 0x12345678);
+        assert_eq!(reader.offset(), 4);
     }
 
     #[test]
@@ -54,9 +160,57 @@ mod tests {
     fn test_read_f32() {
         let value: f32 = 3.14;
         let bytes = value.to_le_bytes();
-        let (read_val, _) = read_f32(&bytes, 0);
+        let read_val = BinaryReader::new(&bytes, 0).read_f32().unwrap();
         assert!((read_val - 3.14).abs() < 1e-6);
     }
 
-    // todo: add tests for read_ixx functions
+    #[test]
+    fn test_read_signed() {
+        let data = [0xff, 0xfe, 0xff, 0x9a, 0xff, 0xff, 0xff];
+        let mut reader = BinaryReader::new(&data, 0);
+        assert_eq!(reader.read_i8().unwrap(), -1);
+        assert_eq!(reader.read_i16().unwrap(), -2);
+        assert_eq!(reader.read_i32().unwrap(), -102);
+    }
+
+    #[test]
+    fn test_read_past_end() {
+        let data = [1, 2, 3];
+        let mut reader = BinaryReader::new(&data, 1);
+        reader.set_section(Section::Buildings);
+        reader.set_building(Some(7));
+        match reader.read_i32().unwrap_err() {
+            BlueprintError::TruncatedData {
+                location,
+                needed,
+                available,
+            } => {
+                assert_eq!(location.section, Section::Buildings);
+                assert_eq!(location.offset, 1);
+                assert_eq!(location.building, Some(7));
+                assert_eq!((needed, available), (4, 2));
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+        // a failed read doesn't move the cursor
+        assert_eq!(reader.read_i16().unwrap(), 0x0302);
+        assert!(reader.read_i8().is_err());
+        assert!(BinaryReader::new(&data, 10).read_i8().is_err());
+    }
+
+    #[test]
+    fn test_write_read_round_trip() {
+        let mut data = Vec::new();
+        write_i8(&mut data, -5);
+        write_i16(&mut data, -1234);
+        write_i32(&mut data, -102);
+        write_f32(&mut data, 0.5);
+        assert_eq!(data.len(), 1 + 2 + 4 + 4);
+        let mut reader = BinaryReader::new(&data, 0);
+        assert_eq!(reader.read_i8().unwrap(), -5);
+        assert_eq!(reader.read_i16().unwrap(), -1234);
+        assert_eq!(reader.read_i32().unwrap(), -102);
+        assert_eq!(reader.read_f32().unwrap(), 0.5);
+        assert_eq!(reader.remaining(), 0);
+    }
 }
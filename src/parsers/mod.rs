@@ -3,5 +3,5 @@
  * All rights reserved.
  */
 
-pub(crate) mod factory_bp;
+pub mod factory_bp;
 // todo: add dyson sphere blueprint parser